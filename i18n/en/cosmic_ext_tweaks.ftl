@@ -3,6 +3,7 @@ app-description = A tool to customize your COSMIC desktop experience.
 
 # Pages
 home = Home
+style = Style
 dock = Dock
 panel = Panel
 color-schemes = Color schemes
@@ -12,6 +13,32 @@ snapshots = Snapshots
 
 color-schemes-error = Error loading color schemes
 import-color-scheme = Import color scheme
+other-formats = Other color scheme formats
+import = Import
+import-from-url = Import from URL
+import-url-description = An https:// or file:// URL of a color scheme file
+paste-color-scheme = Paste color scheme
+pasted-color-scheme = Pasted color scheme
+clipboard = Clipboard
+clipboard-empty = The clipboard does not contain text
+import-results = Imported color schemes
+import-installed = Installed
+import-collision = A color scheme with this name is already installed
+replace = Replace
+keep-both = Keep both
+dismiss = Dismiss
+color-scheme-packs = Color scheme packs
+select-for-pack = Select for a pack
+export-pack = Export pack ({ $count })
+pack-conflicts = Already installed
+pack-conflicts-description = These color schemes in the pack have the same names as installed ones.
+skip = Skip
+overwrite = Overwrite
+generate-from-image = Generate from image
+generate-from-wallpaper = Generate from wallpaper
+images = Images
+save-generated-color-scheme = Save generated color scheme
+save-generated-color-scheme-description = The generated color scheme is saved with a light and a dark variant.
 delete-color-scheme = Delete color scheme
 available-color-schemes-body = Find and install color schemes
 install-color-scheme = Install color scheme
@@ -19,10 +46,55 @@ set-color-scheme = Set color scheme
 find-color-schemes = Find color schemes
 open-containing-folder = Open containing folder
 open-link = Open link
+open-source-page = Open source page
+add-to-favorites = Add to favorites
+remove-from-favorites = Remove from favorites
+color-scheme-properties = Rename, tag or duplicate
+tag = Tag
+tags = Tags
+tags-description = Separate tags with commas
+favorites-first = Favorites first
+vision = Vision
+normal-vision = Normal
+protanopia = Protanopia
+deuteranopia = Deuteranopia
+tritanopia = Tritanopia
+achromatopsia = Achromatopsia
+rename = Rename
+duplicate = Duplicate
+generate-light-variant = Generate a light variant
+generate-dark-variant = Generate a dark variant
+compare = Compare
+compare-color-schemes = Compare color schemes
+available-color-scheme = { $name } (available)
+only-differences = Only differences
+pick-two-color-schemes = Pick two color schemes to compare
+no-differences = These color schemes are identical
+yes = Yes
+no = No
+update-available = Update available, click to update
+update = Update
+update-unknown = Updates unknown
+update-unknown-description = Installed without an id at its provider, so updates cannot be checked
 installed = Installed
 available = Available
 loading = Loading...
+catalogue-error = Could not fetch color schemes
+showing-cached-color-schemes = Offline, showing color schemes saved on { $date }
+retry = Retry
 show-more = Show more
+search-color-schemes = Search color schemes
+sort-name = Name
+sort-newest = Newest
+sort-most-downloaded = Most downloaded
+sort-author = Author
+all = All
+problems = Problems
+problems-description = These files in the themes directories could not be read as color schemes.
+problem-at = Line { $line }, column { $column }: { $error }
+open-file = Open file
+quarantine-file = Move out of the themes directory
+delete-file = Delete file
 
 show-panel = Show panel
 force-icon-buttons-in-panel = Force icon buttons in panel
@@ -33,14 +105,72 @@ padding-description = Padding is the space between the contents and the borders
 spacing = Spacing
 spacing-description = Spacing is the space between the icons in the dock or panel.
 
+interface-style = Interface style
+roundness = Roundness
+roundness-description = How rounded the corners of windows, buttons and other widgets are.
+square = Square
+slightly-rounded = Slightly rounded
+round = Round
+density = Density
+density-description = How much space there is around and between widgets.
+compact = Compact
+standard = Standard
+spacious = Spacious
+outer-gap = Outer gap
+inner-gap = Inner gap
+keep-style = Keep style when applying color schemes
+keep-style-description = Applying a color scheme only changes colors, not roundness, density or gaps.
+
 save = Save
 cancel = Cancel
 close = Close
 create = Create
 
 navigation = Navigation
+mock-window-title = Window
+mock-sidebar-item = Folders
+mock-heading = Heading
+mock-body = Body text
+mock-link = Link
+mock-delete = Delete
+mock-text-input = Text input
 
 save-current-color-scheme = Save current color scheme
+edit-color-scheme = Edit color scheme
+edit-color-scheme-title = Editing { $name }
+apply-temporarily = Apply temporarily
+save-as-new = Save as new
+export = Export
+export-as = Export as…
+export-preview-image = Export preview image
+format = Format
+cosmic-ron = COSMIC (RON)
+contrast-passes = All text and controls meet the WCAG AA contrast ratios
+contrast-fails = These pairs are below the WCAG AA contrast ratio they need
+contrast-background-text = Text on background
+contrast-primary-text = Text on primary container
+contrast-secondary-text = Text on secondary container
+contrast-card-text = Text on cards
+contrast-accent-text = Accent text on background
+contrast-accent-primary-text = Accent text on primary container
+contrast-button-text = Button text
+contrast-accent-button-text = Suggested button text
+opposite-contrast-fails = { $name } was saved, but these pairs of its generated mode are below the WCAG AA contrast ratio they need
+reset = Reset
+palette-default = Palette default
+colors = Colors
+accent-color = Accent color
+background-color = Background color
+primary-container-color = Primary container color
+neutral-tint = Neutral tint
+text-tint = Text tint
+window-hint = Window hint color
+corner-radii = Corner radii
+window-management = Window management
+outer-gap = Outer gap
+inner-gap = Inner gap
+active-hint = Active window hint
+frosted-glass = Frosted glass
 color-scheme-name = Color scheme name
 create-snapshot = Create snapshot
 create-snapshot-description = You are about to create a snapshot, this will save the current state of your desktop and make it possible to restore it later on.
@@ -67,6 +197,27 @@ website = Website
 ## Settings
 settings = Settings
 
+### Color scheme providers
+schedule = Schedule
+schedule-enabled = Switch color schemes automatically
+schedule-description = Apply one color scheme during the day and another at night
+day-color-scheme = Day color scheme
+night-color-scheme = Night color scheme
+switch-at = Switch at
+fixed-times = Fixed times
+sunrise-sunset = Sunrise and sunset
+day-starts = Day starts
+night-starts = Night starts
+latitude = Latitude
+longitude = Longitude
+color-scheme-providers = Color scheme providers
+provider = Provider
+add-provider = Add provider
+provider-location = URL or path
+json-index = JSON index
+local-directory = Local directory
+add = Add
+
 ### Appearance
 appearance = Appearance
 theme = Theme
@@ -1,6 +1,11 @@
 use crate::pages::{self, color_schemes::config::ColorScheme};
 use ::cosmic::{app::Task, widget, Element};
 
+use crate::core::{
+    icons,
+    schedule::{Schedule, Trigger},
+};
+
 use cosmic::Cosmic;
 use message::Message;
 
@@ -17,6 +22,7 @@ pub struct App {
     handler: ::cosmic::cosmic_config::Config,
     config: crate::core::config::TweaksConfig,
     color_schemes: pages::ColorSchemes,
+    style: pages::Style,
     dock: pages::Dock,
     panel: pages::Panel,
     layouts: pages::Layouts,
@@ -32,22 +38,201 @@ impl App {
         )])
     }
 
+    /// Why the action of the open dialog failed, shown under its controls.
+    fn dialog_error(&self) -> Option<Element<Message>> {
+        self.cosmic
+            .dialog_error
+            .as_ref()
+            .map(|error| widget::text::caption(error.clone()).into())
+    }
+
+    fn update_provider_names(&mut self) {
+        self.cosmic.provider_names = self
+            .config
+            .all_providers()
+            .iter()
+            .map(|provider| provider.name().to_string())
+            .collect();
+    }
+
+    fn set_schedule(&mut self, schedule: Schedule) {
+        if let Err(err) = self.config.set_schedule(&self.handler, schedule) {
+            log::warn!("failed to save config: {}", err);
+        }
+    }
+
+    /// Fill the schedule text inputs from the config.
+    fn update_schedule_inputs(&mut self) {
+        match &self.config.schedule.trigger {
+            Trigger::Fixed { day, night } => {
+                self.cosmic.schedule_day_starts = day.format("%H:%M").to_string();
+                self.cosmic.schedule_night_starts = night.format("%H:%M").to_string();
+            }
+            Trigger::Sun {
+                latitude,
+                longitude,
+            } => {
+                self.cosmic.schedule_latitude = latitude.to_string();
+                self.cosmic.schedule_longitude = longitude.to_string();
+            }
+        }
+    }
+
+    fn schedule_section(&self) -> Element<Message> {
+        let schedule = &self.config.schedule;
+        let names = self.color_schemes.installed_names();
+        let position = |name: &Option<String>| {
+            name.as_ref()
+                .and_then(|name| names.iter().position(|n| n == name))
+        };
+
+        let section = widget::settings::section()
+            .title(crate::fl!("schedule"))
+            .add(
+                widget::settings::item::builder(crate::fl!("schedule-enabled"))
+                    .description(crate::fl!("schedule-description"))
+                    .toggler(schedule.enabled, Message::ScheduleEnabled),
+            )
+            .add(
+                widget::settings::item::builder(crate::fl!("day-color-scheme")).control(
+                    widget::dropdown(names, position(&schedule.day), Message::ScheduleDay),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(crate::fl!("night-color-scheme")).control(
+                    widget::dropdown(names, position(&schedule.night), Message::ScheduleNight),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(crate::fl!("switch-at")).control(widget::dropdown(
+                    &self.cosmic.schedule_triggers,
+                    Some(match schedule.trigger {
+                        Trigger::Fixed { .. } => 0,
+                        Trigger::Sun { .. } => 1,
+                    }),
+                    Message::ScheduleTrigger,
+                )),
+            );
+
+        match schedule.trigger {
+            Trigger::Fixed { .. } => section
+                .add(widget::settings::item(
+                    crate::fl!("day-starts"),
+                    widget::text_input("07:00", &self.cosmic.schedule_day_starts)
+                        .on_input(Message::ScheduleDayStarts)
+                        .width(80.0),
+                ))
+                .add(widget::settings::item(
+                    crate::fl!("night-starts"),
+                    widget::text_input("19:00", &self.cosmic.schedule_night_starts)
+                        .on_input(Message::ScheduleNightStarts)
+                        .width(80.0),
+                )),
+            Trigger::Sun { .. } => section
+                .add(widget::settings::item(
+                    crate::fl!("latitude"),
+                    widget::text_input("0.0", &self.cosmic.schedule_latitude)
+                        .on_input(Message::ScheduleLatitude)
+                        .width(80.0),
+                ))
+                .add(widget::settings::item(
+                    crate::fl!("longitude"),
+                    widget::text_input("0.0", &self.cosmic.schedule_longitude)
+                        .on_input(Message::ScheduleLongitude)
+                        .width(80.0),
+                )),
+        }
+        .into()
+    }
+
+    fn providers_section(&self) -> Element<Message> {
+        let spacing = ::cosmic::theme::spacing();
+        let selected_provider = self
+            .config
+            .all_providers()
+            .iter()
+            .position(|provider| *provider == self.config.provider);
+
+        let providers = self
+            .config
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                widget::settings::item(
+                    provider.name(),
+                    widget::button::icon(icons::get_handle("user-trash-symbolic", 14))
+                        .class(::cosmic::style::Button::Destructive)
+                        .on_press(Message::RemoveProvider(index)),
+                )
+                .into()
+            })
+            .collect::<Vec<Element<Message>>>();
+
+        widget::settings::section()
+            .title(crate::fl!("color-scheme-providers"))
+            .add(
+                widget::settings::item::builder(crate::fl!("provider")).control(widget::dropdown(
+                    &self.cosmic.provider_names,
+                    selected_provider,
+                    Message::SelectProvider,
+                )),
+            )
+            .extend(providers)
+            .add(
+                widget::column()
+                    .push(widget::text::heading(crate::fl!("add-provider")))
+                    .push(
+                        widget::text_input(crate::fl!("name"), &self.cosmic.new_provider_name)
+                            .on_input(Message::NewProviderName),
+                    )
+                    .push(
+                        widget::text_input(
+                            crate::fl!("provider-location"),
+                            &self.cosmic.new_provider_location,
+                        )
+                        .on_input(Message::NewProviderLocation)
+                        .on_submit(|_| Message::AddProvider),
+                    )
+                    .push(
+                        widget::row()
+                            .push(widget::dropdown(
+                                &self.cosmic.provider_kinds,
+                                Some(self.cosmic.new_provider_kind),
+                                Message::NewProviderKind,
+                            ))
+                            .push(widget::horizontal_space())
+                            .push(
+                                widget::button::standard(crate::fl!("add"))
+                                    .on_press(Message::AddProvider),
+                            )
+                            .spacing(spacing.space_xxs),
+                    )
+                    .spacing(spacing.space_xxs),
+            )
+            .into()
+    }
+
     fn settings(&self) -> Element<Message> {
         let app_theme_selected = match self.config.app_theme {
             crate::core::config::AppTheme::Dark => 1,
             crate::core::config::AppTheme::Light => 2,
             crate::core::config::AppTheme::System => 0,
         };
-        widget::settings::view_column(vec![widget::settings::section()
-            .title(crate::fl!("appearance"))
-            .add(
-                widget::settings::item::builder(crate::fl!("theme")).control(widget::dropdown(
-                    &self.cosmic.app_themes,
-                    Some(app_theme_selected),
-                    Message::AppTheme,
-                )),
-            )
-            .into()])
+        widget::settings::view_column(vec![
+            widget::settings::section()
+                .title(crate::fl!("appearance"))
+                .add(
+                    widget::settings::item::builder(crate::fl!("theme")).control(widget::dropdown(
+                        &self.cosmic.app_themes,
+                        Some(app_theme_selected),
+                        Message::AppTheme,
+                    )),
+                )
+                .into(),
+            self.schedule_section(),
+            self.providers_section(),
+        ])
         .into()
     }
 }
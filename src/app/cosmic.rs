@@ -1,6 +1,8 @@
 use std::{
     any::TypeId,
     collections::{HashMap, VecDeque},
+    path::PathBuf,
+    time::Duration,
 };
 
 use cosmic::{
@@ -10,7 +12,7 @@ use cosmic::{
     iced::{
         event,
         keyboard::{Event as KeyEvent, Modifiers},
-        Alignment, Event, Length, Subscription,
+        window, Alignment, Event, Length, Subscription,
     },
     widget::{
         self,
@@ -21,6 +23,8 @@ use cosmic::{
     Application, ApplicationExt, Apply, Element, Task,
 };
 
+use chrono::{Local, NaiveTime};
+
 use super::flags::Flags;
 use super::message::Message;
 use super::page::Page;
@@ -32,16 +36,21 @@ use crate::{
         config::{AppTheme, CONFIG_VERSION},
         icons,
         key_bindings::KeyBindings,
+        schedule::{Schedule, Slot, Trigger},
     },
     fl,
     pages::{
         self,
-        color_schemes::{self, ColorSchemes, Status, Tab},
+        color_schemes::{
+            self, export::ExportFormat, labels, pack::Conflict, ColorSchemeProvider, ColorSchemes,
+            Status, Tab,
+        },
         dock::Dock,
         layouts::Layouts,
         panel::Panel,
         shortcuts::Shortcuts,
         snapshots::{config::SnapshotKind, Snapshots},
+        style::Style,
     },
 };
 
@@ -50,11 +59,27 @@ pub struct Cosmic {
     nav_model: segmented_button::SingleSelectModel,
     about: About,
     dialog_pages: VecDeque<DialogPage>,
+    /// Why the action of the open dialog failed.
+    pub dialog_error: Option<String>,
     dialog_text_input: widget::Id,
     key_binds: HashMap<KeyBind, TweaksAction>,
     modifiers: Modifiers,
     context_page: ContextPage,
     pub app_themes: Vec<String>,
+    pub provider_names: Vec<String>,
+    pub provider_kinds: Vec<String>,
+    pub export_formats: Vec<String>,
+    pub conflict_options: Vec<String>,
+    pub new_provider_name: String,
+    pub new_provider_location: String,
+    pub new_provider_kind: usize,
+    pub schedule_triggers: Vec<String>,
+    pub schedule_day_starts: String,
+    pub schedule_night_starts: String,
+    pub schedule_latitude: String,
+    pub schedule_longitude: String,
+    /// The slot and color scheme the schedule applied last, applied again only when they change.
+    pub schedule_applied: Option<(Slot, String)>,
 }
 
 impl Application for App {
@@ -112,29 +137,65 @@ impl Application for App {
                 nav_model,
                 about,
                 dialog_pages: VecDeque::new(),
+                dialog_error: None,
                 dialog_text_input: widget::Id::unique(),
                 key_binds: KeyBindings::new(),
                 modifiers: Modifiers::empty(),
                 context_page: ContextPage::About,
                 app_themes: vec![fl!("match-desktop"), fl!("dark"), fl!("light")],
+                provider_names: flags
+                    .config
+                    .all_providers()
+                    .iter()
+                    .map(|provider| provider.name().to_string())
+                    .collect(),
+                provider_kinds: vec![fl!("json-index"), fl!("local-directory")],
+                export_formats: ExportFormat::all()
+                    .iter()
+                    .map(ExportFormat::title)
+                    .collect(),
+                conflict_options: Conflict::all().iter().map(Conflict::title).collect(),
+                new_provider_name: String::new(),
+                new_provider_location: String::new(),
+                new_provider_kind: 0,
+                schedule_triggers: vec![fl!("fixed-times"), fl!("sunrise-sunset")],
+                schedule_day_starts: String::new(),
+                schedule_night_starts: String::new(),
+                schedule_latitude: String::new(),
+                schedule_longitude: String::new(),
+                schedule_applied: None,
             },
             handler: flags.handler,
             config: flags.config,
             color_schemes: ColorSchemes::default(),
             layouts: Layouts::default(),
+            style: Style::default(),
             dock: Dock::default(),
             panel: Panel::default(),
             snapshots: Snapshots::default(),
             shorcuts: Shortcuts::new(),
         };
 
+        app.update_schedule_inputs();
+        app.color_schemes.keep_style = app.config.keep_style;
+        // Opening the app is not a switch of the schedule, so a color scheme picked
+        // by hand stays until the next one.
+        app.cosmic.schedule_applied = app
+            .config
+            .schedule
+            .scheduled_at(Local::now())
+            .map(|(slot, name)| (slot, name.to_string()));
+
         let mut tasks = vec![
             app.update(Message::ColorSchemes(Box::new(
                 color_schemes::Message::FetchAvailableColorSchemes(
-                    color_schemes::ColorSchemeProvider::CosmicThemes,
+                    app.config.provider.clone(),
                     app.color_schemes.limit,
                 ),
             ))),
+            app.update(Message::ColorSchemes(Box::new(
+                color_schemes::Message::ReloadColorSchemes,
+            ))),
             app.update(Message::Snapshots(
                 pages::snapshots::Message::CreateSnapshot(
                     fl!("application-opened"),
@@ -179,8 +240,17 @@ impl Application for App {
         Some(&self.cosmic.nav_model)
     }
 
+    fn on_app_exit(&mut self) -> Option<Self::Message> {
+        self.color_schemes.close_editor();
+        None
+    }
+
     fn on_nav_select(&mut self, id: widget::nav_bar::Id) -> app::Task<Self::Message> {
         self.cosmic.nav_model.activate(id);
+        // Leaving the page closes the editor, undoing a temporary apply.
+        if self.cosmic.nav_model.data::<Page>(id) != Some(&Page::ColorSchemes) {
+            self.color_schemes.close_editor();
+        }
 
         let title = if let Some(page) = self.cosmic.nav_model.data::<Page>(id) {
             format!("{} - {}", page.title(), fl!("app-title"))
@@ -237,6 +307,189 @@ impl Application for App {
                                 })
                                 .on_submit(|_| Message::DialogComplete),
                         )
+                        .push_maybe(self.dialog_error())
+                        .spacing(spacing.space_xxs),
+                ),
+            DialogPage::PackConflicts(conflicts) => widget::dialog()
+                .title(fl!("pack-conflicts"))
+                .body(fl!("pack-conflicts-description"))
+                .primary_action(
+                    widget::button::suggested(fl!("import")).on_press(Message::DialogComplete),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .control(conflicts.iter().enumerate().fold(
+                    widget::column().spacing(spacing.space_xxs),
+                    |column, (index, (color_scheme, conflict))| {
+                        column.push(
+                            widget::row()
+                                .push(
+                                    widget::text::body(color_scheme.name.clone())
+                                        .width(Length::Fill),
+                                )
+                                .push(widget::dropdown(
+                                    &self.cosmic.conflict_options,
+                                    Conflict::all().iter().position(|c| c == conflict),
+                                    move |option| {
+                                        Message::PackConflict(index, Conflict::all()[option])
+                                    },
+                                ))
+                                .align_y(Alignment::Center)
+                                .spacing(spacing.space_xxs),
+                        )
+                    },
+                )),
+            DialogPage::ImportUrl(url) => widget::dialog()
+                .title(fl!("import-from-url"))
+                .body(fl!("import-url-description"))
+                .primary_action(
+                    widget::button::suggested(fl!("import")).on_press_maybe(
+                        ["https://", "file://"]
+                            .iter()
+                            .any(|scheme| url.trim().starts_with(scheme))
+                            .then_some(Message::DialogComplete),
+                    ),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .control(
+                    widget::text_input("https://", url.as_str())
+                        .id(self.cosmic.dialog_text_input.clone())
+                        .on_input(move |url| Message::DialogUpdate(DialogPage::ImportUrl(url)))
+                        .on_submit(|_| Message::DialogComplete),
+                ),
+            DialogPage::SaveEditedColorScheme(name) => widget::dialog()
+                .title(fl!("save-as-new"))
+                .primary_action(
+                    widget::button::suggested(fl!("save"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .control(
+                    widget::column()
+                        .push(widget::text::body(fl!("color-scheme-name")))
+                        .push(
+                            widget::text_input("", name.as_str())
+                                .id(self.cosmic.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    Message::DialogUpdate(DialogPage::SaveEditedColorScheme(name))
+                                })
+                                .on_submit(|_| Message::DialogComplete),
+                        )
+                        .push_maybe(self.dialog_error())
+                        .spacing(spacing.space_xxs),
+                ),
+            DialogPage::SaveGeneratedColorScheme(name) => widget::dialog()
+                .title(fl!("save-generated-color-scheme"))
+                .body(fl!("save-generated-color-scheme-description"))
+                .primary_action(
+                    widget::button::suggested(fl!("save"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .control(
+                    widget::column()
+                        .push(widget::text::body(fl!("color-scheme-name")))
+                        .push(
+                            widget::text_input("", name.as_str())
+                                .id(self.cosmic.dialog_text_input.clone())
+                                .on_input(move |name| {
+                                    Message::DialogUpdate(DialogPage::SaveGeneratedColorScheme(
+                                        name,
+                                    ))
+                                })
+                                .on_submit(|_| Message::DialogComplete),
+                        )
+                        .push_maybe(self.dialog_error())
+                        .spacing(spacing.space_xxs),
+                ),
+            DialogPage::ExportColorScheme(color_scheme, format) => widget::dialog()
+                .title(fl!("export-as"))
+                .body(color_scheme.name.clone())
+                .primary_action(
+                    widget::button::suggested(fl!("export"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .tertiary_action(
+                    widget::button::text(fl!("export-preview-image"))
+                        .on_press(Message::DialogTertiary),
+                )
+                .control(
+                    widget::column()
+                        .push(widget::text::body(fl!("format")))
+                        .push(widget::dropdown(
+                            &self.cosmic.export_formats,
+                            ExportFormat::all().iter().position(|f| f == format),
+                            {
+                                let color_scheme = color_scheme.clone();
+                                move |index| {
+                                    Message::DialogUpdate(DialogPage::ExportColorScheme(
+                                        color_scheme.clone(),
+                                        ExportFormat::all()[index],
+                                    ))
+                                }
+                            },
+                        ))
+                        .spacing(spacing.space_xxs),
+                ),
+            DialogPage::ColorSchemeProperties(color_scheme, name, tags) => widget::dialog()
+                .title(fl!("color-scheme-properties"))
+                .body(color_scheme.name.clone())
+                .primary_action(
+                    widget::button::suggested(fl!("save"))
+                        .on_press_maybe(Some(Message::DialogComplete)),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("cancel")).on_press(Message::DialogCancel),
+                )
+                .tertiary_action(
+                    widget::button::text(fl!("duplicate")).on_press(Message::DialogTertiary),
+                )
+                .control(
+                    widget::column()
+                        .push(widget::text::body(fl!("color-scheme-name")))
+                        .push(
+                            widget::text_input("", name.as_str())
+                                .id(self.cosmic.dialog_text_input.clone())
+                                .on_input({
+                                    let color_scheme = color_scheme.clone();
+                                    let tags = tags.clone();
+                                    move |name| {
+                                        Message::DialogUpdate(DialogPage::ColorSchemeProperties(
+                                            color_scheme.clone(),
+                                            name,
+                                            tags.clone(),
+                                        ))
+                                    }
+                                })
+                                .on_submit(|_| Message::DialogComplete),
+                        )
+                        .push(widget::text::body(fl!("tags")))
+                        .push(
+                            widget::text_input(fl!("tags-description"), tags.as_str())
+                                .on_input({
+                                    let color_scheme = color_scheme.clone();
+                                    let name = name.clone();
+                                    move |tags| {
+                                        Message::DialogUpdate(DialogPage::ColorSchemeProperties(
+                                            color_scheme.clone(),
+                                            name.clone(),
+                                            tags,
+                                        ))
+                                    }
+                                })
+                                .on_submit(|_| Message::DialogComplete),
+                        )
+                        .push_maybe(self.dialog_error())
                         .spacing(spacing.space_xxs),
                 ),
             DialogPage::CreateSnapshot(name) => widget::dialog()
@@ -277,6 +530,7 @@ impl Application for App {
                 .view()
                 .map(Box::new)
                 .map(Message::ColorSchemes),
+            Page::Style => self.style.view(self.config.keep_style).map(Message::Style),
             Page::Dock => self.dock.view().map(Message::Dock),
             Page::Panel => self.panel.view().map(Message::Panel),
             Page::Layouts => self.layouts.view().map(Message::Layouts),
@@ -297,6 +551,7 @@ impl Application for App {
         let spacing = cosmic::theme::spacing();
 
         match self.cosmic.nav_model.active_data::<Page>() {
+            Some(Page::ColorSchemes) if self.color_schemes.is_editing() => None,
             Some(Page::ColorSchemes) => match self.color_schemes.model.active_data::<Tab>() {
                 Some(Tab::Installed) => Some(
                     widget::row()
@@ -308,6 +563,20 @@ impl Application for App {
                                     color_schemes::Message::SaveCurrentColorScheme(None),
                                 ))),
                         )
+                        .push(
+                            widget::button::standard(fl!("generate-from-wallpaper")).on_press(
+                                Message::ColorSchemes(Box::new(
+                                    color_schemes::Message::GenerateFromWallpaper,
+                                )),
+                            ),
+                        )
+                        .push(
+                            widget::button::standard(fl!("generate-from-image")).on_press(
+                                Message::ColorSchemes(Box::new(
+                                    color_schemes::Message::StartGenerate,
+                                )),
+                            ),
+                        )
                         .push(
                             widget::button::standard(fl!("import-color-scheme"))
                                 .trailing_icon(icons::get_handle("document-save-symbolic", 16))
@@ -315,6 +584,20 @@ impl Application for App {
                                     color_schemes::Message::StartImport,
                                 ))),
                         )
+                        .push(
+                            widget::button::standard(fl!("paste-color-scheme"))
+                                .trailing_icon(icons::get_handle("edit-paste-symbolic", 16))
+                                .on_press(Message::ColorSchemes(Box::new(
+                                    color_schemes::Message::PasteColorScheme,
+                                ))),
+                        )
+                        .push(
+                            widget::button::standard(fl!("import-from-url"))
+                                .trailing_icon(icons::get_handle("insert-link-symbolic", 16))
+                                .on_press(Message::ColorSchemes(Box::new(
+                                    color_schemes::Message::ImportUrl(None),
+                                ))),
+                        )
                         .spacing(spacing.space_xxs)
                         .apply(widget::container)
                         .class(cosmic::style::Container::Card)
@@ -332,7 +615,7 @@ impl Application for App {
                                 ))
                                 .on_press(Message::ColorSchemes(Box::new(
                                     color_schemes::Message::FetchAvailableColorSchemes(
-                                        color_schemes::ColorSchemeProvider::CosmicThemes,
+                                        self.config.provider.clone(),
                                         self.color_schemes.limit,
                                     ),
                                 ))),
@@ -371,6 +654,186 @@ impl Application for App {
                 };
                 tasks.push(self.update_config());
             }
+            Message::SelectProvider(index) => {
+                let Some(provider) = self.config.all_providers().get(index).cloned() else {
+                    return Task::none();
+                };
+                if let Err(err) = self.config.set_provider(&self.handler, provider.clone()) {
+                    log::warn!("failed to save config: {}", err);
+                }
+                tasks.push(self.update(Message::ColorSchemes(Box::new(
+                    color_schemes::Message::SelectProvider(provider),
+                ))));
+            }
+            Message::RemoveProvider(index) => {
+                let mut providers = self.config.providers.clone();
+                if index >= providers.len() {
+                    return Task::none();
+                }
+                let removed = providers.remove(index);
+                if let Err(err) = self.config.set_providers(&self.handler, providers) {
+                    log::warn!("failed to save config: {}", err);
+                }
+                if removed == self.config.provider {
+                    tasks.push(self.update(Message::SelectProvider(0)));
+                }
+                self.update_provider_names();
+            }
+            Message::AddProvider => {
+                let name = self.cosmic.new_provider_name.trim().to_string();
+                let location = self.cosmic.new_provider_location.trim().to_string();
+                if name.is_empty() || location.is_empty() {
+                    return Task::none();
+                }
+                let provider = match self.cosmic.new_provider_kind {
+                    0 => ColorSchemeProvider::JsonIndex {
+                        name,
+                        url: location,
+                    },
+                    _ => ColorSchemeProvider::Local {
+                        name,
+                        path: PathBuf::from(location.trim_start_matches("file://")),
+                    },
+                };
+                let mut providers = self.config.providers.clone();
+                providers.push(provider);
+                if let Err(err) = self.config.set_providers(&self.handler, providers) {
+                    log::warn!("failed to save config: {}", err);
+                }
+                self.cosmic.new_provider_name.clear();
+                self.cosmic.new_provider_location.clear();
+                self.update_provider_names();
+            }
+            Message::ScheduleEnabled(enabled) => {
+                let schedule = Schedule {
+                    enabled,
+                    ..self.config.schedule.clone()
+                };
+                self.set_schedule(schedule);
+                tasks.push(self.update(Message::ScheduleTick));
+            }
+            Message::ScheduleDay(index) => {
+                let schedule = Schedule {
+                    day: self.color_schemes.installed_names().get(index).cloned(),
+                    ..self.config.schedule.clone()
+                };
+                self.set_schedule(schedule);
+                tasks.push(self.update(Message::ScheduleTick));
+            }
+            Message::ScheduleNight(index) => {
+                let schedule = Schedule {
+                    night: self.color_schemes.installed_names().get(index).cloned(),
+                    ..self.config.schedule.clone()
+                };
+                self.set_schedule(schedule);
+                tasks.push(self.update(Message::ScheduleTick));
+            }
+            Message::ScheduleTrigger(index) => {
+                let trigger = match index {
+                    0 => Trigger::default(),
+                    _ => Trigger::Sun {
+                        latitude: self.cosmic.schedule_latitude.trim().parse().unwrap_or(0.0),
+                        longitude: self.cosmic.schedule_longitude.trim().parse().unwrap_or(0.0),
+                    },
+                };
+                let schedule = Schedule {
+                    trigger,
+                    ..self.config.schedule.clone()
+                };
+                self.set_schedule(schedule);
+                tasks.push(self.update(Message::ScheduleTick));
+                self.update_schedule_inputs();
+            }
+            Message::ScheduleDayStarts(input) => {
+                let time = NaiveTime::parse_from_str(input.trim(), "%H:%M").ok();
+                self.cosmic.schedule_day_starts = input;
+                if let (Some(time), Trigger::Fixed { night, .. }) =
+                    (time, &self.config.schedule.trigger)
+                {
+                    let schedule = Schedule {
+                        trigger: Trigger::Fixed {
+                            day: time,
+                            night: *night,
+                        },
+                        ..self.config.schedule.clone()
+                    };
+                    self.set_schedule(schedule);
+                    tasks.push(self.update(Message::ScheduleTick));
+                }
+            }
+            Message::ScheduleNightStarts(input) => {
+                let time = NaiveTime::parse_from_str(input.trim(), "%H:%M").ok();
+                self.cosmic.schedule_night_starts = input;
+                if let (Some(time), Trigger::Fixed { day, .. }) =
+                    (time, &self.config.schedule.trigger)
+                {
+                    let schedule = Schedule {
+                        trigger: Trigger::Fixed {
+                            day: *day,
+                            night: time,
+                        },
+                        ..self.config.schedule.clone()
+                    };
+                    self.set_schedule(schedule);
+                    tasks.push(self.update(Message::ScheduleTick));
+                }
+            }
+            Message::ScheduleLatitude(input) => {
+                let latitude = input.trim().parse::<f64>().ok();
+                self.cosmic.schedule_latitude = input;
+                if let (Some(latitude), Trigger::Sun { longitude, .. }) =
+                    (latitude, &self.config.schedule.trigger)
+                {
+                    if (-90.0..=90.0).contains(&latitude) {
+                        let schedule = Schedule {
+                            trigger: Trigger::Sun {
+                                latitude,
+                                longitude: *longitude,
+                            },
+                            ..self.config.schedule.clone()
+                        };
+                        self.set_schedule(schedule);
+                        tasks.push(self.update(Message::ScheduleTick));
+                    }
+                }
+            }
+            Message::ScheduleLongitude(input) => {
+                let longitude = input.trim().parse::<f64>().ok();
+                self.cosmic.schedule_longitude = input;
+                if let (Some(longitude), Trigger::Sun { latitude, .. }) =
+                    (longitude, &self.config.schedule.trigger)
+                {
+                    if (-180.0..=180.0).contains(&longitude) {
+                        let schedule = Schedule {
+                            trigger: Trigger::Sun {
+                                latitude: *latitude,
+                                longitude,
+                            },
+                            ..self.config.schedule.clone()
+                        };
+                        self.set_schedule(schedule);
+                        tasks.push(self.update(Message::ScheduleTick));
+                    }
+                }
+            }
+            Message::ScheduleTick => {
+                let scheduled = self
+                    .config
+                    .schedule
+                    .scheduled_at(Local::now())
+                    .map(|(slot, name)| (slot, name.to_string()));
+                if scheduled != self.cosmic.schedule_applied {
+                    if let Some((_, name)) = &scheduled {
+                        tasks.push(self.update(Message::ColorSchemes(Box::new(
+                            color_schemes::Message::ScheduledColorScheme(name.clone()),
+                        ))));
+                    }
+                    self.cosmic.schedule_applied = scheduled;
+                }
+            }
+            Message::NewProviderName(name) => self.cosmic.new_provider_name = name,
+            Message::NewProviderLocation(location) => self.cosmic.new_provider_location = location,
+            Message::NewProviderKind(kind) => self.cosmic.new_provider_kind = kind,
             Message::ToggleContextPage(page) => {
                 if self.cosmic.context_page == page {
                     self.core_mut().window.show_context = !self.core().window.show_context;
@@ -382,6 +845,15 @@ impl Application for App {
             Message::ToggleContextDrawer => {
                 self.core_mut().window.show_context = !self.core().window.show_context;
             }
+            Message::Style(pages::style::Message::KeepStyle(keep_style)) => {
+                if let Err(err) = self.config.set_keep_style(&self.handler, keep_style) {
+                    log::warn!("failed to save config: {}", err);
+                }
+                self.color_schemes.keep_style = keep_style;
+            }
+            Message::Style(message) => {
+                tasks.push(self.style.update(message).map(cosmic::action::app))
+            }
             Message::Dock(message) => {
                 tasks.push(self.dock.update(message).map(cosmic::action::app))
             }
@@ -406,6 +878,67 @@ impl Application for App {
                         DialogPage::SaveCurrentColorScheme(String::new()),
                     )))
                 }
+                pages::color_schemes::Message::SaveEditedColorScheme(None) => {
+                    tasks.push(self.update(Message::ToggleDialogPage(
+                        DialogPage::SaveEditedColorScheme(String::new()),
+                    )))
+                }
+                pages::color_schemes::Message::PackConflicts(color_schemes) => tasks.push(
+                    self.update(Message::ToggleDialogPage(DialogPage::PackConflicts(
+                        color_schemes
+                            .into_iter()
+                            .map(|color_scheme| (color_scheme, Conflict::default()))
+                            .collect(),
+                    ))),
+                ),
+                pages::color_schemes::Message::ImportUrl(None) => tasks.push(self.update(
+                    Message::ToggleDialogPage(DialogPage::ImportUrl(String::new())),
+                )),
+                pages::color_schemes::Message::CloseDialog => {
+                    self.cosmic.dialog_pages.pop_front();
+                    self.cosmic.dialog_error = None;
+                }
+                pages::color_schemes::Message::DialogError(error) => {
+                    self.cosmic.dialog_error = Some(error);
+                }
+                pages::color_schemes::Message::Renamed(from, to) => {
+                    let rename = |name: &Option<String>| match name {
+                        Some(name) if *name == from => Some(to.clone()),
+                        name => name.clone(),
+                    };
+                    let schedule = Schedule {
+                        day: rename(&self.config.schedule.day),
+                        night: rename(&self.config.schedule.night),
+                        ..self.config.schedule.clone()
+                    };
+                    if schedule != self.config.schedule {
+                        self.set_schedule(schedule);
+                    }
+                    // Not a switch of the schedule, so the renamed color scheme is not applied again.
+                    if let Some((_, name)) = &mut self.cosmic.schedule_applied {
+                        if *name == from {
+                            *name = to;
+                        }
+                    }
+                }
+                pages::color_schemes::Message::SaveGeneratedColorScheme(None) => {
+                    tasks.push(self.update(Message::ToggleDialogPage(
+                        DialogPage::SaveGeneratedColorScheme(String::new()),
+                    )))
+                }
+                pages::color_schemes::Message::EditProperties(color_scheme) => {
+                    let name = color_scheme.name.clone();
+                    let tags = self.color_schemes.labels().tags(&name).join(", ");
+                    tasks.push(self.update(Message::ToggleDialogPage(
+                        DialogPage::ColorSchemeProperties(Box::new(color_scheme), name, tags),
+                    )))
+                }
+                pages::color_schemes::Message::ExportColorScheme(color_scheme, None) => tasks.push(
+                    self.update(Message::ToggleDialogPage(DialogPage::ExportColorScheme(
+                        Box::new(color_scheme),
+                        ExportFormat::default(),
+                    ))),
+                ),
                 _ => tasks.push(
                     self.color_schemes
                         .update(*message)
@@ -427,13 +960,66 @@ impl Application for App {
             }
             Message::DialogUpdate(dialog_page) => {
                 self.cosmic.dialog_pages[0] = dialog_page;
+                self.cosmic.dialog_error = None;
+            }
+            Message::PackConflict(index, conflict) => {
+                if let Some(DialogPage::PackConflicts(conflicts)) =
+                    self.cosmic.dialog_pages.front_mut()
+                {
+                    if let Some((_, resolution)) = conflicts.get_mut(index) {
+                        *resolution = conflict;
+                    }
+                }
             }
             Message::DialogComplete => {
-                if let Some(dialog_page) = self.cosmic.dialog_pages.pop_front() {
+                let dialog_page = match self.cosmic.dialog_pages.front() {
+                    Some(dialog_page) if dialog_page.waits_for_result() => {
+                        Some(dialog_page.clone())
+                    }
+                    _ => self.cosmic.dialog_pages.pop_front(),
+                };
+                if let Some(dialog_page) = dialog_page {
                     match dialog_page {
                         DialogPage::SaveCurrentColorScheme(name) => {
                             tasks.push(self.update(Message::SaveNewColorScheme(name)))
                         }
+                        DialogPage::SaveEditedColorScheme(name) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::SaveEditedColorScheme(Some(name)),
+                            ))))
+                        }
+                        DialogPage::PackConflicts(conflicts) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::ResolvePackConflicts(conflicts),
+                            ))))
+                        }
+                        DialogPage::ImportUrl(url) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::ImportUrl(Some(url)),
+                            ))))
+                        }
+                        DialogPage::SaveGeneratedColorScheme(name) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::SaveGeneratedColorScheme(Some(name)),
+                            ))))
+                        }
+                        DialogPage::ExportColorScheme(color_scheme, format) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::ExportColorScheme(
+                                    *color_scheme,
+                                    Some(format),
+                                ),
+                            ))))
+                        }
+                        DialogPage::ColorSchemeProperties(color_scheme, name, tags) => {
+                            tasks.push(self.update(Message::ColorSchemes(Box::new(
+                                pages::color_schemes::Message::SaveProperties(
+                                    *color_scheme,
+                                    name,
+                                    labels::parse_tags(&tags),
+                                ),
+                            ))))
+                        }
                         DialogPage::CreateSnapshot(name) => {
                             tasks.push(self.update(Message::Snapshots(
                                 pages::snapshots::Message::CreateSnapshot(name, SnapshotKind::User),
@@ -444,7 +1030,27 @@ impl Application for App {
             }
             Message::DialogCancel => {
                 self.cosmic.dialog_pages.pop_front();
+                self.cosmic.dialog_error = None;
             }
+            Message::DialogTertiary => match self.cosmic.dialog_pages.front().cloned() {
+                // Stays open until the page reports whether the duplicate was installed.
+                Some(DialogPage::ColorSchemeProperties(color_scheme, name, tags)) => {
+                    tasks.push(self.update(Message::ColorSchemes(Box::new(
+                        pages::color_schemes::Message::DuplicateColorScheme(
+                            *color_scheme,
+                            name,
+                            labels::parse_tags(&tags),
+                        ),
+                    ))))
+                }
+                Some(DialogPage::ExportColorScheme(color_scheme, _)) => {
+                    self.cosmic.dialog_pages.pop_front();
+                    tasks.push(self.update(Message::ColorSchemes(Box::new(
+                        pages::color_schemes::Message::ExportPreviewImage(*color_scheme),
+                    ))))
+                }
+                _ => (),
+            },
             Message::Key(modifiers, key) => {
                 for (key_bind, action) in &self.cosmic.key_binds {
                     if key_bind.matches(modifiers, &key) {
@@ -455,6 +1061,13 @@ impl Application for App {
             Message::Modifiers(modifiers) => {
                 self.cosmic.modifiers = modifiers;
             }
+            Message::FileDropped(path) => {
+                if self.cosmic.nav_model.active_data::<Page>() == Some(&Page::ColorSchemes) {
+                    tasks.push(self.update(Message::ColorSchemes(Box::new(
+                        pages::color_schemes::Message::ImportFiles(vec![path]),
+                    ))));
+                }
+            }
             Message::SystemThemeModeChange => {
                 tasks.push(self.update_config());
             }
@@ -474,6 +1087,7 @@ impl Application for App {
                 Event::Keyboard(KeyEvent::ModifiersChanged(modifiers)) => {
                     Some(Message::Modifiers(modifiers))
                 }
+                Event::Window(window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
                 _ => None,
             }),
             cosmic_config::config_subscription(
@@ -506,6 +1120,9 @@ impl Application for App {
                 }
                 Message::SystemThemeModeChange
             }),
+            cosmic::iced::time::every(Duration::from_secs(60)).map(|_| Message::ScheduleTick),
+            pages::color_schemes::watcher::subscription()
+                .map(|message| Message::ColorSchemes(Box::new(message))),
         ];
 
         Subscription::batch(subscriptions)
@@ -1,5 +1,30 @@
-#[derive(Clone, Debug, Eq, PartialEq)]
+use crate::pages::color_schemes::{config::ColorScheme, export::ExportFormat, pack::Conflict};
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum DialogPage {
     SaveCurrentColorScheme(String),
+    SaveEditedColorScheme(String),
+    SaveGeneratedColorScheme(String),
+    /// The URL being typed.
+    ImportUrl(String),
+    ExportColorScheme(Box<ColorScheme>, ExportFormat),
+    /// The color scheme with the name and comma separated tags being typed.
+    ColorSchemeProperties(Box<ColorScheme>, String, String),
+    /// Color schemes of a pack that are already installed, with what to do with each.
+    PackConflicts(Vec<(ColorScheme, Conflict)>),
     CreateSnapshot(String),
 }
+
+impl DialogPage {
+    /// Whether the dialog stays open until the page reports if its action succeeded,
+    /// so a failure can be shown in it.
+    pub fn waits_for_result(&self) -> bool {
+        matches!(
+            self,
+            Self::SaveCurrentColorScheme(_)
+                | Self::SaveEditedColorScheme(_)
+                | Self::SaveGeneratedColorScheme(_)
+                | Self::ColorSchemeProperties(..)
+        )
+    }
+}
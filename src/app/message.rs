@@ -5,6 +5,7 @@ use cosmic::iced::keyboard::{Key, Modifiers};
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    Style(pages::style::Message),
     Dock(pages::dock::Message),
     Panel(pages::panel::Message),
     Layouts(pages::layouts::Message),
@@ -14,13 +15,35 @@ pub enum Message {
     DialogUpdate(DialogPage),
     DialogComplete,
     DialogCancel,
+    /// The tertiary action of the dialog, duplicating from the properties dialog
+    /// and exporting a preview image from the export dialog.
+    DialogTertiary,
+    /// How one color scheme of the pack conflicts dialog is resolved, by its index.
+    PackConflict(usize, pages::color_schemes::pack::Conflict),
     SaveNewColorScheme(String),
     ToggleContextPage(ContextPage),
     ToggleContextDrawer,
     ToggleDialogPage(DialogPage),
     AppTheme(usize),
+    SelectProvider(usize),
+    RemoveProvider(usize),
+    AddProvider,
+    NewProviderName(String),
+    NewProviderLocation(String),
+    NewProviderKind(usize),
+    ScheduleEnabled(bool),
+    ScheduleDay(usize),
+    ScheduleNight(usize),
+    ScheduleTrigger(usize),
+    ScheduleDayStarts(String),
+    ScheduleNightStarts(String),
+    ScheduleLatitude(String),
+    ScheduleLongitude(String),
+    ScheduleTick,
     Key(Modifiers, Key),
     Modifiers(Modifiers),
+    /// A file dropped on the window, imported when the color schemes page is open.
+    FileDropped(std::path::PathBuf),
     SystemThemeModeChange,
     Open(String),
 }
@@ -8,6 +8,7 @@ use crate::core::icons;
 pub enum Page {
     #[default]
     ColorSchemes,
+    Style,
     Dock,
     Panel,
     Layouts,
@@ -25,6 +26,7 @@ impl Page {
     pub fn title(&self) -> String {
         match self {
             Self::ColorSchemes => fl!("color-schemes"),
+            Self::Style => fl!("style"),
             Self::Dock => fl!("dock"),
             Self::Panel => fl!("panel"),
             Self::Layouts => fl!("layouts"),
@@ -36,6 +38,7 @@ impl Page {
     pub fn icon(&self) -> Icon {
         match self {
             Self::ColorSchemes => icons::get_icon("dark-mode-symbolic", 18),
+            Self::Style => icons::get_icon("applications-graphics-symbolic", 18),
             Self::Dock => icons::get_icon("dock-bottom-symbolic", 18),
             Self::Panel => icons::get_icon("dock-top-symbolic", 18),
             Self::Layouts => icons::get_icon("view-coverflow-symbolic", 18),
@@ -47,6 +50,7 @@ impl Page {
     pub fn all() -> &'static [Self] {
         &[
             Self::ColorSchemes,
+            Self::Style,
             Self::Dock,
             Self::Panel,
             Self::Layouts,
@@ -0,0 +1,66 @@
+use clap::{Parser, Subcommand};
+use serde_json::{json, Value};
+
+use crate::Error;
+
+mod layout;
+mod schedule;
+mod scheme;
+mod shortcuts;
+mod snapshot;
+
+/// Command-line interface for scripting tweaks without opening a window.
+///
+/// Every command prints JSON to stdout on success. Failures print a JSON
+/// object with an `error` key to stderr and exit with a non-zero code.
+#[derive(Debug, Parser)]
+#[command(name = "cosmic-ext-tweaks", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manage color schemes
+    #[command(subcommand)]
+    Scheme(scheme::SchemeCommand),
+    /// Apply panel and dock layouts
+    #[command(subcommand)]
+    Layout(layout::LayoutCommand),
+    /// Manage snapshots of the desktop configuration
+    #[command(subcommand)]
+    Snapshot(snapshot::SnapshotCommand),
+    /// Apply shortcut presets
+    #[command(subcommand)]
+    Shortcuts(shortcuts::ShortcutsCommand),
+    /// Switch color schemes on a schedule
+    #[command(subcommand)]
+    Schedule(schedule::ScheduleCommand),
+}
+
+impl Command {
+    fn run(self) -> Result<Value, Error> {
+        match self {
+            Command::Scheme(command) => command.run(),
+            Command::Layout(command) => command.run(),
+            Command::Snapshot(command) => command.run(),
+            Command::Shortcuts(command) => command.run(),
+            Command::Schedule(command) => command.run(),
+        }
+    }
+}
+
+/// Run a command and return the exit code for the process.
+pub fn run(command: Command) -> i32 {
+    match command.run() {
+        Ok(output) => {
+            println!("{output}");
+            0
+        }
+        Err(err) => {
+            eprintln!("{}", json!({ "error": err.to_string() }));
+            1
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::{pages::layouts::config::Layout, Error};
+
+#[derive(Debug, Subcommand)]
+pub enum LayoutCommand {
+    /// List the available layouts
+    List,
+    /// Apply a layout by name
+    Apply { name: String },
+}
+
+impl LayoutCommand {
+    pub fn run(self) -> Result<Value, Error> {
+        match self {
+            Self::List => Ok(Value::Array(
+                Layout::list()
+                    .iter()
+                    .map(|layout| json!({ "name": layout.name() }))
+                    .collect(),
+            )),
+            Self::Apply { name } => {
+                let layout = Layout::find(&name)?;
+                layout.apply()?;
+                Ok(json!({ "name": layout.name() }))
+            }
+        }
+    }
+}
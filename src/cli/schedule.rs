@@ -0,0 +1,68 @@
+use chrono::Local;
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::{
+    core::{config::TweaksConfig, schedule},
+    pages::color_schemes::config::ColorScheme,
+    Error,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum ScheduleCommand {
+    /// Show the color scheme the schedule wants now and when it changes next
+    Status,
+    /// Keep applying the scheduled color scheme, meant to run in the background
+    Run,
+}
+
+impl ScheduleCommand {
+    pub fn run(self) -> Result<Value, Error> {
+        match self {
+            Self::Status => {
+                let schedule = TweaksConfig::new().schedule;
+                let now = Local::now();
+                Ok(json!({
+                    "enabled": schedule.enabled,
+                    "color_scheme": schedule.color_scheme_at(now),
+                    "next_change": schedule.next_change(now).map(|next| next.to_rfc3339()),
+                }))
+            }
+            Self::Run => {
+                // Only apply when the slot or its color scheme changes, so a color
+                // scheme picked by hand stays until the next switch.
+                let mut applied = None;
+                loop {
+                    // Read the config every time so changes made in the app are picked up.
+                    let TweaksConfig {
+                        schedule,
+                        keep_style,
+                        ..
+                    } = TweaksConfig::new();
+                    let now = Local::now();
+                    let scheduled = schedule
+                        .scheduled_at(now)
+                        .map(|(slot, name)| (slot, name.to_string()));
+                    if scheduled != applied {
+                        match &scheduled {
+                            Some((_, name)) => match ColorScheme::find(name)
+                                .and_then(|scheme| scheme.apply(keep_style))
+                            {
+                                Ok(()) => {
+                                    log::info!("switched to the scheduled color scheme {name}");
+                                    applied = scheduled.clone();
+                                }
+                                // Left unapplied, so it is tried again on the next poll.
+                                Err(e) => {
+                                    log::error!("failed to apply the color scheme {name}: {e}")
+                                }
+                            },
+                            None => applied = None,
+                        }
+                    }
+                    std::thread::sleep(schedule::poll_interval(&schedule, now));
+                }
+            }
+        }
+    }
+}
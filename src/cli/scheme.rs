@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use cosmic::cosmic_theme::ThemeBuilder;
+use serde_json::{json, Value};
+
+use crate::{
+    core::config::TweaksConfig,
+    pages::color_schemes::{config::ColorScheme, contrast, convert, export::ExportFormat, render},
+    Error,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum SchemeCommand {
+    /// List the installed color schemes
+    List,
+    /// Select an installed color scheme and apply it
+    Apply { name: String },
+    /// Install a color scheme from a RON, base16/base24, terminal theme or pywal file
+    Install {
+        path: PathBuf,
+        /// Name to install the color scheme as, defaults to the file name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Report the WCAG contrast ratios of an installed color scheme
+    Contrast { name: String },
+    /// Export an installed color scheme as RON, or in a format for other applications
+    Export {
+        name: String,
+        #[arg(short, long, value_enum, default_value_t)]
+        format: ExportFormat,
+        /// File to write the color scheme to, the content is printed if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Render a preview of an installed color scheme to a PNG image
+    Render {
+        name: String,
+        /// File to write the PNG image to
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Width of the image in pixels
+        #[arg(
+            short,
+            long,
+            default_value_t = render::DEFAULT_WIDTH,
+            value_parser = clap::value_parser!(u32).range(1..=render::MAX_WIDTH as i64),
+        )]
+        width: u32,
+    },
+}
+
+impl SchemeCommand {
+    pub fn run(self) -> Result<Value, Error> {
+        match self {
+            Self::List => {
+                let selected = ColorScheme::selected();
+                let color_schemes = ColorScheme::installed()
+                    .iter()
+                    .map(|color_scheme| summary(color_scheme, &selected))
+                    .collect();
+                Ok(Value::Array(color_schemes))
+            }
+            Self::Apply { name } => {
+                let color_scheme = ColorScheme::find(&name)?;
+                color_scheme.apply(TweaksConfig::new().keep_style)?;
+                Ok(summary(&color_scheme, &color_scheme))
+            }
+            Self::Install { path, name } => {
+                let (theme, variant) = convert::import(&path, &std::fs::read_to_string(&path)?)?;
+                let name = name
+                    .or_else(|| {
+                        path.file_stem()
+                            .and_then(|name| name.to_str())
+                            .map(|name| name.to_string())
+                    })
+                    .ok_or(Error::ThemePathNotFound)?;
+                let mut color_scheme = ColorScheme {
+                    name,
+                    path: None,
+                    link: None,
+                    author: None,
+                    theme,
+                    variant,
+                    provenance: None,
+                };
+                color_scheme.path = Some(color_scheme.install()?);
+                Ok(summary(&color_scheme, &ColorScheme::selected()))
+            }
+            Self::Contrast { name } => {
+                let color_scheme = ColorScheme::find(&name)?;
+                let theme = color_scheme.theme.build();
+                let variant = color_scheme.variant.map(ThemeBuilder::build);
+                let checks = contrast::report(&theme, variant.as_ref())
+                    .into_iter()
+                    .map(|check| {
+                        json!({
+                            "pair": check.key,
+                            "ratio": check.ratio,
+                            "minimum": check.minimum,
+                            "passes": check.passes(),
+                        })
+                    })
+                    .collect();
+                Ok(Value::Array(checks))
+            }
+            Self::Export {
+                name,
+                format,
+                output,
+            } => {
+                let content = format.export(&ColorScheme::find(&name)?)?;
+                match output {
+                    Some(output) => {
+                        std::fs::write(&output, content)?;
+                        Ok(json!({ "name": name, "path": output }))
+                    }
+                    None => Ok(json!({ "name": name, "content": content })),
+                }
+            }
+            Self::Render {
+                name,
+                output,
+                width,
+            } => {
+                let png = render::render(&ColorScheme::find(&name)?, width)?;
+                std::fs::write(&output, png)?;
+                Ok(json!({ "name": name, "path": output }))
+            }
+        }
+    }
+}
+
+fn summary(color_scheme: &ColorScheme, selected: &ColorScheme) -> Value {
+    json!({
+        "name": color_scheme.name,
+        "path": color_scheme.path,
+        "author": color_scheme.author,
+        "link": color_scheme.link,
+        "variants": color_scheme.variant.is_some(),
+        "selected": color_scheme.name == selected.name,
+    })
+}
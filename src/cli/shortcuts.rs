@@ -0,0 +1,33 @@
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::{pages::shortcuts::Shortcut, Error};
+
+#[derive(Debug, Subcommand)]
+pub enum ShortcutsCommand {
+    /// List the shortcut presets
+    List,
+    /// Replace the custom shortcuts with a preset
+    Apply { preset: String },
+}
+
+impl ShortcutsCommand {
+    pub fn run(self) -> Result<Value, Error> {
+        match self {
+            Self::List => Ok(Value::Array(
+                Shortcut::list()
+                    .iter()
+                    .map(|shortcut| json!({ "name": shortcut.name() }))
+                    .collect(),
+            )),
+            Self::Apply { preset } => {
+                let shortcut = Shortcut::list()
+                    .into_iter()
+                    .find(|shortcut| shortcut.name().eq_ignore_ascii_case(&preset))
+                    .ok_or_else(|| Error::NotFound("shortcut preset", preset))?;
+                shortcut.apply()?;
+                Ok(json!({ "name": shortcut.name() }))
+            }
+        }
+    }
+}
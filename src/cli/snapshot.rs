@@ -0,0 +1,42 @@
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::{
+    pages::snapshots::{config::SnapshotKind, Snapshots},
+    Error,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCommand {
+    /// List the saved snapshots
+    List,
+    /// Save the current desktop configuration as a snapshot
+    Create { name: String },
+    /// Restore a snapshot by name
+    Restore { name: String },
+    /// Delete a snapshot by name
+    Delete { name: String },
+}
+
+impl SnapshotCommand {
+    pub fn run(self) -> Result<Value, Error> {
+        let mut snapshots = Snapshots::default();
+        match self {
+            Self::List => Ok(serde_json::to_value(&snapshots.config.snapshots)?),
+            Self::Create { name } => {
+                let snapshot = snapshots.create(&name, SnapshotKind::User)?;
+                Ok(serde_json::to_value(snapshot)?)
+            }
+            Self::Restore { name } => {
+                let snapshot = snapshots.find(&name)?;
+                Snapshots::restore(&snapshot)?;
+                Ok(serde_json::to_value(snapshot)?)
+            }
+            Self::Delete { name } => {
+                let snapshot = snapshots.find(&name)?;
+                snapshots.delete(&snapshot)?;
+                Ok(json!({ "name": snapshot.name }))
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use cosmic::cosmic_theme::palette::{FromColor, IntoColor, Oklab, Oklch, Srgb, Srgba};
+
+/// Parse a `#rrggbb` or `#rrggbbaa` hex color, the leading `#` is optional.
+pub fn parse_hex(hex: &str) -> Option<Srgba> {
+    let hex = hex.trim().trim_start_matches('#');
+    let channel = |index: usize| {
+        u8::from_str_radix(hex.get(index..index + 2)?, 16)
+            .ok()
+            .map(|channel| channel as f32 / 255.0)
+    };
+
+    match hex.len() {
+        6 => Some(Srgba::new(channel(0)?, channel(2)?, channel(4)?, 1.0)),
+        8 => Some(Srgba::new(
+            channel(0)?,
+            channel(2)?,
+            channel(4)?,
+            channel(6)?,
+        )),
+        _ => None,
+    }
+}
+
+/// Format a color as `#rrggbb`, or `#rrggbbaa` when it is translucent.
+pub fn to_hex(color: Srgba) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let rgb = format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue)
+    );
+
+    if color.alpha < 1.0 {
+        format!("{rgb}{:02x}", channel(color.alpha))
+    } else {
+        rgb
+    }
+}
+
+/// Convert an sRGB channel to linear light.
+fn to_linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light channel back to sRGB.
+fn from_linear(channel: f32) -> f32 {
+    let channel = channel.clamp(0.0, 1.0);
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// WCAG relative luminance, from 0 for black to 1 for white.
+pub fn relative_luminance(color: Srgba) -> f32 {
+    0.2126 * to_linear(color.red) + 0.7152 * to_linear(color.green) + 0.0722 * to_linear(color.blue)
+}
+
+/// Multiply the linear RGB channels of a color by a 3×3 matrix, keeping its alpha.
+pub fn transform_linear(color: Srgba, matrix: &[[f32; 3]; 3]) -> Srgba {
+    let rgb = [
+        to_linear(color.red),
+        to_linear(color.green),
+        to_linear(color.blue),
+    ];
+    let [red, green, blue] =
+        matrix.map(|row| from_linear(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]));
+    Srgba::new(red, green, blue, color.alpha)
+}
+
+/// WCAG 2.x contrast ratio between two colors, from 1 to 21.
+pub fn contrast_ratio(a: Srgba, b: Srgba) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Blend a translucent color over an opaque one.
+pub fn composite(color: Srgba, over: Srgba) -> Srgba {
+    let mix = |top: f32, bottom: f32| top * color.alpha + bottom * (1.0 - color.alpha);
+    Srgba::new(
+        mix(color.red, over.red),
+        mix(color.green, over.green),
+        mix(color.blue, over.blue),
+        1.0,
+    )
+}
+
+/// Perceptual distance between two colors, the Euclidean distance in Oklab
+/// times 100. Differences below about 2 are hard to see.
+pub fn difference(a: Srgba, b: Srgba) -> f32 {
+    let a: Oklab = a.color.into_color();
+    let b: Oklab = b.color.into_color();
+    ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt() * 100.0
+}
+
+/// Perceptual lightness of a color, its OKLCH `L` from 0 to 1.
+pub fn lightness(color: Srgba) -> f32 {
+    Oklch::from_color(color.color).l
+}
+
+/// The color with its OKLCH lightness replaced, keeping the hue. Chroma is reduced
+/// until the color fits in sRGB.
+pub fn with_lightness(color: Srgba, lightness: f32) -> Srgba {
+    let mut oklch = Oklch::from_color(color.color);
+    oklch.l = lightness.clamp(0.0, 1.0);
+    loop {
+        let rgb = Srgb::from_color(oklch);
+        let in_gamut = [rgb.red, rgb.green, rgb.blue]
+            .iter()
+            .all(|channel| (-0.0001..=1.0001).contains(channel));
+        if in_gamut || oklch.chroma < 0.001 {
+            return Srgba::new(
+                rgb.red.clamp(0.0, 1.0),
+                rgb.green.clamp(0.0, 1.0),
+                rgb.blue.clamp(0.0, 1.0),
+                color.alpha,
+            );
+        }
+        oklch.chroma *= 0.9;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(value: f32) -> Srgba {
+        Srgba::new(value, value, value, 1.0)
+    }
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn relative_luminance_of_black_white_and_primaries() {
+        assert_near(relative_luminance(gray(0.0)), 0.0);
+        assert_near(relative_luminance(gray(1.0)), 1.0);
+        assert_near(relative_luminance(Srgba::new(1.0, 0.0, 0.0, 1.0)), 0.2126);
+        assert_near(relative_luminance(Srgba::new(0.0, 1.0, 0.0, 1.0)), 0.7152);
+        assert_near(relative_luminance(Srgba::new(0.0, 0.0, 1.0, 1.0)), 0.0722);
+    }
+
+    #[test]
+    fn contrast_ratio_matches_wcag() {
+        assert_near(contrast_ratio(gray(0.0), gray(1.0)), 21.0);
+        assert_near(contrast_ratio(gray(0.5), gray(0.5)), 1.0);
+        // #777777 is the usual example of a gray just below 4.5:1 on white.
+        let white = gray(1.0);
+        let gray_77 = parse_hex("777777").unwrap();
+        assert_near(contrast_ratio(gray_77, white), 4.48);
+        assert_eq!(
+            contrast_ratio(gray_77, white),
+            contrast_ratio(white, gray_77)
+        );
+    }
+    #[test]
+    fn transform_linear_with_identity_keeps_the_color() {
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let color = Srgba::new(0.2, 0.5, 0.8, 0.6);
+        let transformed = transform_linear(color, &identity);
+        assert_near(transformed.red, 0.2);
+        assert_near(transformed.green, 0.5);
+        assert_near(transformed.blue, 0.8);
+        assert_eq!(transformed.alpha, 0.6);
+    }
+
+    #[test]
+    fn transform_linear_mixes_in_linear_light() {
+        // Half of full red is 0.5 in linear light, which is about 0.735 in sRGB.
+        let mix = [[0.5, 0.5, 0.0], [0.5, 0.5, 0.0], [0.0, 0.0, 1.0]];
+        let transformed = transform_linear(Srgba::new(1.0, 0.0, 0.0, 1.0), &mix);
+        assert_near(transformed.red, 0.735);
+        assert_near(transformed.green, 0.735);
+        assert_near(transformed.blue, 0.0);
+        // Out of range results are clamped.
+        let double = [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, -1.0]];
+        let transformed = transform_linear(gray(0.8), &double);
+        assert_eq!(transformed.red, 1.0);
+        assert_eq!(transformed.blue, 0.0);
+    }
+    #[test]
+    fn with_lightness_sets_lightness_and_keeps_alpha() {
+        let color = Srgba::new(0.2, 0.4, 0.6, 0.5);
+        for target in [0.2, 0.5, 0.8] {
+            let changed = with_lightness(color, target);
+            assert_near(lightness(changed), target);
+            assert_eq!(changed.alpha, 0.5);
+        }
+        // Grays stay gray.
+        let changed = with_lightness(gray(0.2), 0.7);
+        assert_near(changed.red, changed.green);
+        assert_near(changed.green, changed.blue);
+    }
+
+    #[test]
+    fn with_lightness_stays_in_gamut() {
+        let blue = Srgba::new(0.0, 0.0, 1.0, 1.0);
+        for target in [0.0, 0.95, 1.0, 1.5] {
+            let changed = with_lightness(blue, target);
+            for channel in [changed.red, changed.green, changed.blue] {
+                assert!((0.0..=1.0).contains(&channel), "{channel} at {target}");
+            }
+        }
+        assert_near(lightness(with_lightness(blue, 0.95)), 0.95);
+        assert_near(lightness(with_lightness(blue, 1.5)), 1.0);
+    }
+}
@@ -4,13 +4,21 @@ use cosmic::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::app::App;
+use crate::{app::App, core::schedule::Schedule, pages::color_schemes::ColorSchemeProvider};
 
 pub const CONFIG_VERSION: u64 = 1;
 
-#[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
+#[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
 pub struct TweaksConfig {
     pub app_theme: AppTheme,
+    /// The provider the available color schemes are fetched from.
+    pub provider: ColorSchemeProvider,
+    /// Providers added by the user, cosmic-themes.org is always available.
+    pub providers: Vec<ColorSchemeProvider>,
+    /// Switches color schemes at set times or at sunrise and sunset.
+    pub schedule: Schedule,
+    /// Keep the current roundness, density and gaps when applying a color scheme.
+    pub keep_style: bool,
 }
 
 impl TweaksConfig {
@@ -27,6 +35,13 @@ impl TweaksConfig {
             config
         })
     }
+
+    /// Every provider that can be picked, starting with cosmic-themes.org.
+    pub fn all_providers(&self) -> Vec<ColorSchemeProvider> {
+        std::iter::once(ColorSchemeProvider::CosmicThemes)
+            .chain(self.providers.iter().cloned())
+            .collect()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
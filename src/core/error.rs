@@ -6,6 +6,40 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("RON error: {0}")]
     Ron(#[from] ron::error::SpannedError),
+    #[error("RON serialization error: {0}")]
+    RonSerialize(#[from] ron::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Portal error: {0}")]
+    Portal(#[from] ashpd::Error),
+    #[error("Config error: {0}")]
+    Config(#[from] cosmic::cosmic_config::Error),
+    #[error("Template error: {0}")]
+    Template(String),
     #[error("Theme path not found")]
     ThemePathNotFound,
+    #[error("Data directory not found")]
+    DataDirNotFound,
+    #[error("No {0} named {1:?}")]
+    NotFound(&'static str, String),
+    #[error("A {0} named {1:?} already exists")]
+    AlreadyExists(&'static str, String),
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("{0:?} is not a valid color scheme name")]
+    InvalidName(String),
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("Wallpaper not found")]
+    WallpaperNotFound,
+    #[error("Render error: {0}")]
+    Render(&'static str),
+    #[error("Only https:// and file:// URLs can be imported, not {0:?}")]
+    UnsupportedUrl(String),
+    #[error("Unsupported color scheme format")]
+    UnsupportedFormat,
+    #[error("The download is larger than {0} bytes")]
+    TooLarge(usize),
 }
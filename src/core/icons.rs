@@ -53,13 +53,16 @@ impl IconCache {
         bundle!("snapshots-symbolic", 18);
         bundle!("arrow-into-box-symbolic", 16);
         bundle!("document-save-symbolic", 16);
+        bundle!("document-save-symbolic", 14);
         bundle!("search-global-symbolic", 16);
+        bundle!("search-global-symbolic", 14);
         bundle!("list-add-symbolic", 16);
         bundle!("symbolic-link-symbolic", 14);
         bundle!("user-trash-symbolic", 14);
         bundle!("selection-mode-symbolic", 14);
         bundle!("folder-download-symbolic", 14);
         bundle!("arrow-circular-bottom-right-symbolic", 14);
+        bundle!("arrow-into-box-symbolic", 14);
 
         Self { cache }
     }
@@ -0,0 +1,70 @@
+use cosmic::{
+    cosmic_config::{Config, CosmicConfigEntry},
+    cosmic_theme::{CornerRadii, Spacing, Theme, ThemeBuilder},
+};
+
+use crate::{pages::color_schemes::config::ColorScheme, Error};
+
+/// The interface style fields of a theme, everything but its colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterfaceStyle {
+    pub corner_radii: CornerRadii,
+    pub spacing: Spacing,
+    /// Outer and inner gaps around tiled windows.
+    pub gaps: (u32, u32),
+}
+
+impl InterfaceStyle {
+    /// The style of the theme in a dark or light theme config.
+    pub fn read(config: &Config) -> Self {
+        Self::of(&Theme::get_entry(config).unwrap_or_else(|(_, theme)| theme))
+    }
+
+    fn of(theme: &Theme) -> Self {
+        Self {
+            corner_radii: theme.corner_radii,
+            spacing: theme.spacing,
+            gaps: theme.gaps,
+        }
+    }
+
+    /// The style of the theme in the active theme mode.
+    pub fn current() -> Self {
+        let (config, default) = if ColorScheme::theme_mode().is_dark {
+            (Theme::dark_config(), Theme::dark())
+        } else {
+            (Theme::light_config(), Theme::light())
+        };
+        match config {
+            Ok(config) => Self::read(&config),
+            Err(e) => {
+                log::error!("Failed to read the theme config: {e}");
+                Self::of(&default)
+            }
+        }
+    }
+
+    pub fn apply_to(&self, theme: &mut Theme) {
+        theme.corner_radii = self.corner_radii;
+        theme.spacing = self.spacing;
+        theme.gaps = self.gaps;
+    }
+
+    /// Write the style to the dark and light themes and their builders, keeping their colors.
+    pub fn write(&self) -> Result<(), Error> {
+        for config in [Theme::dark_config()?, Theme::light_config()?] {
+            let mut theme = Theme::get_entry(&config).unwrap_or_else(|(_, theme)| theme);
+            self.apply_to(&mut theme);
+            theme.write_entry(&config)?;
+        }
+        for config in [ThemeBuilder::dark_config()?, ThemeBuilder::light_config()?] {
+            let mut builder =
+                ThemeBuilder::get_entry(&config).unwrap_or_else(|(_, builder)| builder);
+            builder.corner_radii = self.corner_radii;
+            builder.spacing = self.spacing;
+            builder.gaps = self.gaps;
+            builder.write_entry(&config)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
+pub mod color;
 pub mod config;
 pub mod error;
 pub mod grid;
 pub mod icons;
+pub mod interface_style;
 pub mod key_bindings;
 pub mod localize;
 pub mod resources;
+pub mod schedule;
 pub mod settings;
 pub mod style;
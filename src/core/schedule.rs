@@ -0,0 +1,276 @@
+use std::f64::consts::PI;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Switches between a day and a night color scheme.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Schedule {
+    pub enabled: bool,
+    /// Name of the color scheme applied during the day.
+    pub day: Option<String>,
+    /// Name of the color scheme applied during the night.
+    pub night: Option<String>,
+    pub trigger: Trigger,
+}
+
+/// The part of the day a schedule is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slot {
+    Day,
+    Night,
+}
+
+/// When the day starts and ends.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum Trigger {
+    Fixed {
+        day: NaiveTime,
+        night: NaiveTime,
+    },
+    /// Sunrise and sunset, computed locally for a position in degrees, east and north positive.
+    Sun {
+        latitude: f64,
+        longitude: f64,
+    },
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self::Fixed {
+            day: NaiveTime::from_hms_opt(7, 0, 0).unwrap_or_default(),
+            night: NaiveTime::from_hms_opt(19, 0, 0).unwrap_or_default(),
+        }
+    }
+}
+
+impl Trigger {
+    /// Start of the day and of the night on a date, `None` during polar day or night.
+    fn times(&self, date: NaiveDate) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        match self {
+            Self::Fixed { day, night } => {
+                let local =
+                    |time: &NaiveTime| Local.from_local_datetime(&date.and_time(*time)).earliest();
+                Some((local(day)?, local(night)?))
+            }
+            Self::Sun {
+                latitude,
+                longitude,
+            } => {
+                let (sunrise, sunset) = sun_times(date, *latitude, *longitude)?;
+                Some((sunrise.with_timezone(&Local), sunset.with_timezone(&Local)))
+            }
+        }
+    }
+
+    /// Whether it is day at `now`.
+    fn is_day(&self, now: DateTime<Local>) -> bool {
+        match self.times(now.date_naive()) {
+            Some((day, night)) if day <= night => day <= now && now < night,
+            // The night starts before the day, e.g. day at 20:00 for night owls.
+            Some((day, night)) => !(night <= now && now < day),
+            None => match self {
+                Self::Sun { latitude, .. } => polar_day(now.date_naive(), *latitude),
+                Self::Fixed { .. } => true,
+            },
+        }
+    }
+}
+
+impl Schedule {
+    /// Name of the color scheme that should be applied at `now`.
+    pub fn color_scheme_at(&self, now: DateTime<Local>) -> Option<&str> {
+        self.scheduled_at(now).map(|(_, name)| name)
+    }
+
+    /// The part of the day at `now` and the color scheme scheduled for it.
+    ///
+    /// Callers remember what they last applied and only apply again when this
+    /// changes, so a color scheme picked by hand stays until the next switch.
+    pub fn scheduled_at(&self, now: DateTime<Local>) -> Option<(Slot, &str)> {
+        if !self.enabled {
+            return None;
+        }
+        if self.trigger.is_day(now) {
+            Some((Slot::Day, self.day.as_deref()?))
+        } else {
+            Some((Slot::Night, self.night.as_deref()?))
+        }
+    }
+
+    /// The next time the schedule switches color schemes after `now`.
+    pub fn next_change(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let today = now.date_naive();
+        [today, today.succ_opt()?]
+            .into_iter()
+            .filter_map(|date| self.trigger.times(date))
+            .flat_map(|(day, night)| [day, night])
+            .filter(|time| *time > now)
+            .min()
+    }
+}
+
+/// Sunrise and sunset in UTC, following the sunrise equation.
+fn sun_times(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let radians = |degrees: f64| degrees * PI / 180.0;
+    let degrees = |radians: f64| radians * 180.0 / PI;
+
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+    let n = (date - epoch).num_days() as f64 + 0.0008;
+    let mean_solar_noon = n - longitude / 360.0;
+
+    let anomaly = (357.5291 + 0.98560028 * mean_solar_noon).rem_euclid(360.0);
+    let m = radians(anomaly);
+    let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_longitude = radians((anomaly + center + 180.0 + 102.9372).rem_euclid(360.0));
+    let transit =
+        2451545.0 + mean_solar_noon + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic_longitude).sin();
+
+    let declination = (ecliptic_longitude.sin() * radians(23.4397).sin()).asin();
+    let latitude = radians(latitude);
+    let cos_hour_angle = (radians(-0.833).sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = degrees(cos_hour_angle.acos());
+
+    let from_julian = |julian: f64| {
+        let seconds = (julian - 2440587.5) * 86400.0;
+        Utc.timestamp_opt(seconds as i64, 0).single()
+    };
+    Some((
+        from_julian(transit - hour_angle / 360.0)?,
+        from_julian(transit + hour_angle / 360.0)?,
+    ))
+}
+
+/// Whether the sun stays up all day, when it neither rises nor sets.
+fn polar_day(date: NaiveDate, latitude: f64) -> bool {
+    // The sun is over the northern hemisphere between the March and September equinoxes.
+    let northern_summer = (80..266).contains(&date.ordinal());
+    northern_summer == (latitude >= 0.0)
+}
+
+/// How long to wait before checking the schedule again.
+pub fn poll_interval(schedule: &Schedule, now: DateTime<Local>) -> std::time::Duration {
+    let minute = Duration::minutes(1);
+    schedule
+        .next_change(now)
+        .map(|next| (next - now).min(minute))
+        .unwrap_or(minute)
+        .max(Duration::seconds(1))
+        .to_std()
+        .unwrap_or(std::time::Duration::from_secs(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(date: NaiveDate, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .from_local_datetime(&date.and_hms_opt(hour, minute, 0).unwrap())
+            .earliest()
+            .unwrap()
+    }
+
+    fn fixed(day: u32, night: u32) -> Schedule {
+        Schedule {
+            enabled: true,
+            day: Some("Day".into()),
+            night: Some("Night".into()),
+            trigger: Trigger::Fixed {
+                day: NaiveTime::from_hms_opt(day, 0, 0).unwrap(),
+                night: NaiveTime::from_hms_opt(night, 0, 0).unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn sun_times_at_the_equinox() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let (sunrise, sunset) = sun_times(date, 0.0, 0.0).unwrap();
+        assert_eq!(sunrise.format("%H:%M").to_string(), "06:05");
+        assert_eq!(sunset.format("%H:%M").to_string(), "18:11");
+    }
+
+    #[test]
+    fn sun_times_wrap_past_midnight_utc() {
+        // San Francisco sets after midnight UTC in June.
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let (sunrise, sunset) = sun_times(date, 37.77, -122.42).unwrap();
+        assert_eq!(sunrise.date_naive(), date);
+        assert_eq!(sunset.date_naive(), date.succ_opt().unwrap());
+        let daylight = sunset - sunrise;
+        assert!(daylight > Duration::hours(14) && daylight < Duration::hours(15));
+    }
+
+    #[test]
+    fn polar_day_and_night() {
+        let june = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let december = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        // Longyearbyen, Svalbard.
+        assert_eq!(sun_times(june, 78.2, 15.6), None);
+        assert_eq!(sun_times(december, 78.2, 15.6), None);
+        assert!(polar_day(june, 78.2));
+        assert!(!polar_day(december, 78.2));
+        // The seasons are reversed in Antarctica.
+        assert!(!polar_day(june, -78.0));
+        assert!(polar_day(december, -78.0));
+
+        let schedule = Schedule {
+            trigger: Trigger::Sun {
+                latitude: 78.2,
+                longitude: 15.6,
+            },
+            ..fixed(7, 19)
+        };
+        assert_eq!(
+            schedule.scheduled_at(local(june, 12, 0)),
+            Some((Slot::Day, "Day"))
+        );
+        assert_eq!(
+            schedule.scheduled_at(local(december, 12, 0)),
+            Some((Slot::Night, "Night"))
+        );
+    }
+
+    #[test]
+    fn fixed_times() {
+        let schedule = fixed(7, 19);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(schedule.color_scheme_at(local(date, 6, 59)), Some("Night"));
+        assert_eq!(schedule.color_scheme_at(local(date, 7, 0)), Some("Day"));
+        assert_eq!(schedule.color_scheme_at(local(date, 18, 59)), Some("Day"));
+        assert_eq!(schedule.color_scheme_at(local(date, 19, 0)), Some("Night"));
+    }
+
+    #[test]
+    fn fixed_times_wrap_past_midnight() {
+        // The day starts in the evening and the night in the morning.
+        let schedule = fixed(20, 6);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(schedule.color_scheme_at(local(date, 23, 0)), Some("Day"));
+        assert_eq!(schedule.color_scheme_at(local(date, 3, 0)), Some("Day"));
+        assert_eq!(schedule.color_scheme_at(local(date, 12, 0)), Some("Night"));
+        assert_eq!(
+            schedule.next_change(local(date, 23, 0)),
+            Some(local(date.succ_opt().unwrap(), 6, 0))
+        );
+    }
+
+    #[test]
+    fn disabled_schedules_apply_nothing() {
+        let schedule = Schedule {
+            enabled: false,
+            ..fixed(7, 19)
+        };
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(schedule.scheduled_at(local(date, 12, 0)), None);
+    }
+}
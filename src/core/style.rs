@@ -23,21 +23,60 @@ pub fn background<'a>(theme: &Theme) -> cosmic::theme::Container<'a> {
     })
 }
 
-pub fn card<'a>(theme: Theme) -> cosmic::theme::Container<'a> {
-    let theme = theme.clone();
+pub fn swatch<'a>(color: Color) -> cosmic::theme::Container<'a> {
     let corner_radii = cosmic::theme::active().cosmic().corner_radii;
 
-    cosmic::theme::Container::custom(move |_| container::Style {
-        icon_color: Some(Color::from(theme.primary.component.on)),
-        text_color: Some(Color::from(theme.primary.component.on)),
-        background: Some(cosmic::iced::Background::Color(
-            theme.primary.component.base.into(),
-        )),
+    cosmic::theme::Container::custom(move |theme| container::Style {
+        background: Some(Background::Color(color)),
         border: Border {
             radius: corner_radii.radius_s.into(),
+            width: 1.0,
+            color: theme.cosmic().background.divider.into(),
+        },
+        ..Default::default()
+    })
+}
+
+pub fn badge<'a>(background: Color, text: Color) -> cosmic::theme::Container<'a> {
+    let corner_radii = cosmic::theme::active().cosmic().corner_radii;
+
+    cosmic::theme::Container::custom(move |_| container::Style {
+        text_color: Some(text),
+        background: Some(Background::Color(background)),
+        border: Border {
+            radius: corner_radii.radius_xl.into(),
             ..Default::default()
         },
-        shadow: Shadow::default(),
+        ..Default::default()
+    })
+}
+
+/// A plain fill with fixed corners, for drawing widgets of a theme that isn't active.
+pub fn surface<'a>(background: Color, radius: [f32; 4]) -> cosmic::theme::Container<'a> {
+    cosmic::theme::Container::custom(move |_| container::Style {
+        background: Some(Background::Color(background)),
+        border: Border {
+            radius: radius.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// Like `surface`, with a one pixel border.
+pub fn surface_outlined<'a>(
+    background: Color,
+    border: Color,
+    radius: [f32; 4],
+) -> cosmic::theme::Container<'a> {
+    cosmic::theme::Container::custom(move |_| container::Style {
+        background: Some(Background::Color(background)),
+        border: Border {
+            radius: radius.into(),
+            width: 1.0,
+            color: border,
+        },
+        ..Default::default()
     })
 }
 
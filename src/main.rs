@@ -1,11 +1,19 @@
 mod app;
+mod cli;
 mod core;
 mod pages;
 
+use clap::Parser;
 pub use core::error::Error;
 use core::settings;
 
 fn main() -> cosmic::iced::Result {
+    let cli = cli::Cli::parse();
     settings::init();
+
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command));
+    }
+
     cosmic::app::run::<app::App>(settings::settings(), settings::flags())
 }
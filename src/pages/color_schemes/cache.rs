@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use cosmic::cosmic_theme::{Theme, ThemeBuilder};
+
+use crate::Error;
+
+use super::{
+    config::ColorScheme,
+    contrast::{self, ContrastCheck},
+    problems::BrokenFile,
+    vision::Deficiency,
+};
+
+/// The themes of a color scheme and their contrast checks, computed once instead
+/// of on every redraw.
+#[derive(Debug, Clone)]
+pub struct Built {
+    pub theme: Theme,
+    pub variant: Option<Theme>,
+    pub contrast: Vec<ContrastCheck>,
+    /// The same themes as seen with a color vision deficiency, set by `simulate`.
+    simulated: Option<Box<Built>>,
+}
+
+impl Built {
+    pub fn new(theme: &ThemeBuilder, variant: Option<&ThemeBuilder>) -> Self {
+        Self::of(
+            theme.clone().build(),
+            variant.cloned().map(ThemeBuilder::build),
+        )
+    }
+
+    /// Check the contrast of themes that are already built.
+    pub fn of(theme: Theme, variant: Option<Theme>) -> Self {
+        Self {
+            contrast: contrast::report(&theme, variant.as_ref()),
+            theme,
+            variant,
+            simulated: None,
+        }
+    }
+
+    /// Simulate `vision` once, instead of on every redraw of the previews.
+    pub fn simulate(&mut self, vision: Option<Deficiency>) {
+        self.simulated = vision.map(|deficiency| Box::new(deficiency.simulate(self)));
+    }
+
+    /// The themes to preview, simulated if a deficiency was given to `simulate`.
+    pub fn seen(&self) -> &Built {
+        self.simulated.as_deref().unwrap_or(self)
+    }
+}
+
+impl From<&ColorScheme> for Built {
+    fn from(color_scheme: &ColorScheme) -> Self {
+        Self::new(&color_scheme.theme, color_scheme.variant.as_ref())
+    }
+}
+
+/// The result of reading the themes directories in the background.
+#[derive(Debug, Clone, Default)]
+pub struct Installed {
+    pub color_schemes: Vec<ColorScheme>,
+    pub built: HashMap<PathBuf, Built>,
+    pub problems: Vec<BrokenFile>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    modified: SystemTime,
+    color_scheme: ColorScheme,
+    built: Built,
+}
+
+/// Parsed and built color schemes with their provenance, keyed by path and reused
+/// until the file's modification time changes. Provenance is only written along
+/// with the color scheme, so it is fresh as long as the file is.
+#[derive(Debug, Clone, Default)]
+pub struct Cache(Arc<Mutex<HashMap<PathBuf, Entry>>>);
+
+impl Cache {
+    /// Read the installed color schemes on a blocking thread, only parsing
+    /// files that are new or changed since the last load.
+    pub async fn load(self) -> Installed {
+        tokio::task::spawn_blocking(move || self.load_blocking())
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("failed to load the installed color schemes: {e}");
+                Installed::default()
+            })
+    }
+
+    fn load_blocking(&self) -> Installed {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let mut built = HashMap::new();
+        let (color_schemes, problems) = ColorScheme::scan_with(|path| {
+            let entry = Self::read(&mut entries, path)?;
+            built.insert(path.to_path_buf(), entry.built.clone());
+            Ok(entry.color_scheme.clone())
+        });
+        // Forget files that were removed or are broken now.
+        entries.retain(|path, _| built.contains_key(path));
+
+        Installed {
+            color_schemes,
+            built,
+            problems,
+        }
+    }
+
+    fn read<'a>(entries: &'a mut HashMap<PathBuf, Entry>, path: &Path) -> Result<&'a Entry, Error> {
+        let modified = std::fs::metadata(path)?.modified()?;
+        let fresh = entries
+            .get(path)
+            .is_some_and(|entry| entry.modified == modified);
+        if !fresh {
+            let color_scheme = ColorScheme::from_file(path)?;
+            let entry = Entry {
+                modified,
+                built: Built::from(&color_scheme),
+                color_scheme,
+            };
+            entries.insert(path.to_path_buf(), entry);
+        }
+        Ok(&entries[path])
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Utc};
+use cosmic::Application;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{app::App, Error};
+
+use super::{config::ColorScheme, provider::ColorSchemeProvider, query::Query};
+
+/// Cached pages not fetched again for this long are removed.
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// The most the cached pages take up on disk, the least recently fetched go first.
+const MAX_SIZE: u64 = 50 * 1024 * 1024;
+
+/// One page of available color schemes, as fetched from a provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CataloguePage {
+    pub fetched_at: DateTime<Utc>,
+    pub color_schemes: Vec<ColorScheme>,
+}
+
+/// Where a page of available color schemes was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    Network,
+    Cache,
+}
+
+/// Identifies a page of the catalogue, a file in the cache directory.
+#[derive(Debug, Clone)]
+pub struct PageKey(String);
+
+impl PageKey {
+    pub fn new(provider: &ColorSchemeProvider, query: &Query, limit: usize, offset: usize) -> Self {
+        let source = match provider {
+            ColorSchemeProvider::CosmicThemes => ("cosmic-themes", String::new()),
+            ColorSchemeProvider::JsonIndex { url, .. } => ("json-index", url.clone()),
+            ColorSchemeProvider::Local { path, .. } => {
+                ("local", path.to_string_lossy().into_owned())
+            }
+        };
+        // Spelled out rather than derived, so the key stays the same across releases.
+        let key = serde_json::json!([source, query.params(limit, offset)]);
+        Self(format!("{:x}", Sha256::digest(key.to_string())))
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        dir().map(|dir| dir.join(&self.0).with_extension("ron"))
+    }
+
+    /// The page as it was last fetched, if it was ever fetched.
+    pub async fn read(self) -> Option<CataloguePage> {
+        let ron = tokio::fs::read_to_string(self.path()?).await.ok()?;
+        ron::from_str(&ron)
+            .inspect_err(|e| log::error!("failed to read the cached catalogue page: {e}"))
+            .ok()
+    }
+
+    pub async fn write(&self, page: &CataloguePage) -> Result<(), Error> {
+        let path = self.path().ok_or(Error::DataDirNotFound)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(path, ron::to_string(page)?).await?;
+        Ok(())
+    }
+}
+
+fn dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(App::APP_ID).join("catalogue"))
+}
+
+/// Remove cached pages older than `MAX_AGE`, then the oldest ones until the rest
+/// fit in `MAX_SIZE`.
+async fn prune() -> Result<(), Error> {
+    let Some(dir) = dir() else {
+        return Ok(());
+    };
+    let mut pages = vec![];
+    let mut read_dir = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = read_dir.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            pages.push((metadata.modified()?, metadata.len(), entry.path()));
+        }
+    }
+
+    // Newest first, so the size adds up from the pages that are kept.
+    pages.sort_by(|a, b| b.0.cmp(&a.0));
+    let now = SystemTime::now();
+    let mut size = 0;
+    for (modified, len, path) in pages {
+        size += len;
+        let expired = now.duration_since(modified).is_ok_and(|age| age > MAX_AGE);
+        if expired || size > MAX_SIZE {
+            tokio::fs::remove_file(path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Fetch a page from the provider and keep a copy in the cache directory for offline use.
+pub async fn fetch(
+    provider: ColorSchemeProvider,
+    query: Query,
+    limit: usize,
+    offset: usize,
+) -> Result<CataloguePage, Error> {
+    let key = PageKey::new(&provider, &query, limit, offset);
+    let page = CataloguePage {
+        color_schemes: provider.fetch(query, limit, offset).await?,
+        fetched_at: Utc::now(),
+    };
+    if let Err(e) = key.write(&page).await {
+        log::error!("failed to cache the catalogue page: {e}");
+    }
+    if let Err(e) = prune().await {
+        log::error!("failed to prune the catalogue cache: {e}");
+    }
+    Ok(page)
+}
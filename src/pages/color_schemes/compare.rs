@@ -0,0 +1,291 @@
+use cosmic::{
+    cosmic_theme::{palette::Srgba, ThemeBuilder},
+    iced::{Alignment, Color, Length},
+    widget, Apply, Element,
+};
+
+use crate::{
+    core::{
+        color::{difference, to_hex},
+        style,
+    },
+    fl,
+};
+
+use super::{
+    cache::Built,
+    config::ColorScheme,
+    editor::{ColorField, Radius},
+    preview,
+    vision::Deficiency,
+};
+
+/// Compares two color schemes field by field.
+pub struct Compare {
+    /// Installed and available color schemes at the time the view was opened.
+    candidates: Vec<ColorScheme>,
+    options: Vec<String>,
+    /// The picked candidates and their themes, built once when picked.
+    left: Option<(usize, Built)>,
+    right: Option<(usize, Built)>,
+    /// Color vision deficiency the previews are simulated with.
+    vision: Option<Deficiency>,
+    only_differences: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Left(usize),
+    Right(usize),
+    OnlyDifferences(bool),
+}
+
+/// The value of a `ThemeBuilder` field on one side of the comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// `None` when the palette default is used.
+    Color(Option<Srgba>),
+    Number(f32),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDiff {
+    pub field: String,
+    pub left: Value,
+    pub right: Value,
+}
+
+impl Value {
+    fn text(&self) -> String {
+        match self {
+            Self::Color(Some(color)) => to_hex(*color),
+            Self::Color(None) => fl!("palette-default"),
+            Self::Number(number) => format!("{number:.0}"),
+            Self::Bool(true) => fl!("yes"),
+            Self::Bool(false) => fl!("no"),
+        }
+    }
+}
+
+impl FieldDiff {
+    pub fn differs(&self) -> bool {
+        self.left != self.right
+    }
+
+    /// How far apart the values are, as ΔE for colors and the signed change for numbers.
+    pub fn delta(&self) -> Option<String> {
+        if !self.differs() {
+            return None;
+        }
+        match (self.left, self.right) {
+            (Value::Color(Some(left)), Value::Color(Some(right))) => {
+                Some(format!("ΔE {:.1}", difference(left, right)))
+            }
+            (Value::Number(left), Value::Number(right)) => Some(format!("{:+.0}", right - left)),
+            _ => Some(String::from("≠")),
+        }
+    }
+}
+
+/// Every field of two theme builders that a color scheme sets.
+pub fn diff(left: &ThemeBuilder, right: &ThemeBuilder) -> Vec<FieldDiff> {
+    let colors = ColorField::all().iter().map(|field| FieldDiff {
+        field: field.title(),
+        left: Value::Color(field.get(left)),
+        right: Value::Color(field.get(right)),
+    });
+    let radii = Radius::all().iter().map(|radius| FieldDiff {
+        field: format!("{} {}", fl!("corner-radii"), radius.title()),
+        left: Value::Number(radius.get(left)),
+        right: Value::Number(radius.get(right)),
+    });
+    let layout = [
+        (
+            fl!("outer-gap"),
+            Value::Number(left.gaps.0 as f32),
+            Value::Number(right.gaps.0 as f32),
+        ),
+        (
+            fl!("inner-gap"),
+            Value::Number(left.gaps.1 as f32),
+            Value::Number(right.gaps.1 as f32),
+        ),
+        (
+            fl!("active-hint"),
+            Value::Number(left.active_hint as f32),
+            Value::Number(right.active_hint as f32),
+        ),
+        (
+            fl!("frosted-glass"),
+            Value::Bool(left.is_frosted),
+            Value::Bool(right.is_frosted),
+        ),
+        (
+            fl!("dark"),
+            Value::Bool(left.palette.is_dark()),
+            Value::Bool(right.palette.is_dark()),
+        ),
+    ]
+    .into_iter()
+    .map(|(field, left, right)| FieldDiff { field, left, right });
+
+    colors.chain(radii).chain(layout).collect()
+}
+
+impl Compare {
+    /// Compare any of the installed and available color schemes, starting with
+    /// the selected one on the left.
+    pub fn new(
+        installed: &[ColorScheme],
+        available: Vec<ColorScheme>,
+        selected: &str,
+        vision: Option<Deficiency>,
+    ) -> Self {
+        let options = installed
+            .iter()
+            .map(|c| c.name.clone())
+            .chain(
+                available
+                    .iter()
+                    .map(|c| fl!("available-color-scheme", name = c.name.clone())),
+            )
+            .collect();
+        let mut compare = Self {
+            candidates: installed.iter().cloned().chain(available).collect(),
+            options,
+            left: None,
+            right: None,
+            vision,
+            only_differences: true,
+        };
+        compare.left = installed
+            .iter()
+            .position(|c| c.name == selected)
+            .and_then(|index| compare.pick(index));
+        compare
+    }
+
+    fn pick(&self, index: usize) -> Option<(usize, Built)> {
+        let candidate = self.candidates.get(index)?;
+        let mut built = Built::from(candidate);
+        built.simulate(self.vision);
+        Some((index, built))
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Left(index) => self.left = self.pick(index),
+            Message::Right(index) => self.right = self.pick(index),
+            Message::OnlyDifferences(only_differences) => self.only_differences = only_differences,
+        }
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, super::Message> {
+        let spacing = cosmic::theme::spacing();
+        let compare = super::Message::Compare;
+
+        let header = widget::row()
+            .push(widget::text::title3(fl!("compare-color-schemes")))
+            .push(widget::horizontal_space())
+            .push(widget::text::body(fl!("only-differences")))
+            .push(
+                widget::toggler(self.only_differences)
+                    .on_toggle(move |only| compare(Message::OnlyDifferences(only))),
+            )
+            .push(widget::button::standard(fl!("close")).on_press(super::Message::CloseCompare))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        let pickers = widget::row()
+            .push(
+                widget::dropdown(&self.options, picked(&self.left), move |index| {
+                    compare(Message::Left(index))
+                })
+                .width(Length::Fill),
+            )
+            .push(
+                widget::dropdown(&self.options, picked(&self.right), move |index| {
+                    compare(Message::Right(index))
+                })
+                .width(Length::Fill),
+            )
+            .spacing(spacing.space_s);
+
+        let side = |side: &'a Option<(usize, Built)>| {
+            let (index, built) = side.as_ref()?;
+            Some((self.candidates.get(*index)?, built))
+        };
+        let (Some((left, left_built)), Some((right, right_built))) =
+            (side(&self.left), side(&self.right))
+        else {
+            return widget::column()
+                .push(header)
+                .push(pickers)
+                .push(widget::text(fl!("pick-two-color-schemes")))
+                .spacing(spacing.space_xs)
+                .into();
+        };
+
+        let previews = widget::row()
+            .push(preview::preview(&left.name, left_built, &spacing, 280))
+            .push(widget::horizontal_space())
+            .push(preview::preview(&right.name, right_built, &spacing, 280))
+            .spacing(spacing.space_s);
+
+        let rows: Vec<FieldDiff> = diff(&left.theme, &right.theme)
+            .into_iter()
+            .filter(|row| !self.only_differences || row.differs())
+            .collect();
+        let table = if rows.is_empty() {
+            widget::settings::section().add(widget::text(fl!("no-differences")))
+        } else {
+            rows.into_iter()
+                .fold(widget::settings::section(), |section, row| {
+                    let delta = row.delta().unwrap_or_default();
+                    section.add(
+                        widget::row()
+                            .push(widget::text(row.field).width(Length::FillPortion(2)))
+                            .push(value(row.left).width(Length::FillPortion(2)))
+                            .push(value(row.right).width(Length::FillPortion(2)))
+                            .push(widget::text::caption(delta).width(Length::FillPortion(1)))
+                            .align_y(Alignment::Center)
+                            .spacing(spacing.space_xxs),
+                    )
+                })
+        };
+
+        widget::column()
+            .push(header)
+            .push(pickers)
+            .push(previews)
+            .push(widget::scrollable(table))
+            .spacing(spacing.space_xs)
+            .into()
+    }
+}
+
+/// A value with a swatch in front of colors.
+fn value<'a>(value: Value) -> widget::Row<'a, super::Message> {
+    let spacing = cosmic::theme::spacing();
+    let swatch = match value {
+        Value::Color(color) => Some(
+            widget::Space::new(16.0, 16.0)
+                .apply(widget::container)
+                .class(style::swatch(
+                    color.map(Color::from).unwrap_or(Color::TRANSPARENT),
+                )),
+        ),
+        _ => None,
+    };
+    widget::row()
+        .push_maybe(swatch)
+        .push(widget::text::body(value.text()))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxs)
+}
+
+/// The index of a picked candidate, for its dropdown.
+fn picked(side: &Option<(usize, Built)>) -> Option<usize> {
+    side.as_ref().map(|(index, _)| *index)
+}
@@ -1,14 +1,16 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use cosmic::{
     cosmic_config::{self, Config},
-    cosmic_theme::{ThemeBuilder, ThemeMode},
+    cosmic_theme::{Theme, ThemeBuilder, ThemeMode},
 };
 use cosmic_config::cosmic_config_derive::CosmicConfigEntry;
 use cosmic_config::CosmicConfigEntry;
 use serde::{Deserialize, Serialize};
 
-use crate::Error;
+use crate::{core::interface_style::InterfaceStyle, Error};
+
+use super::{problems::BrokenFile, provenance::Provenance};
 
 const CONFIG_ID: &str = "dev.edfloreshz.CosmicTweaks.ColorScheme";
 
@@ -20,6 +22,16 @@ pub struct ColorScheme {
     pub link: Option<String>,
     pub author: Option<String>,
     pub theme: ThemeBuilder,
+    /// The opposite mode of `theme`, for color schemes that come in light and dark pairs.
+    pub variant: Option<ThemeBuilder>,
+    pub provenance: Option<Provenance>,
+}
+
+/// File format for color schemes that carry both a dark and a light variant.
+#[derive(Debug, Serialize, Clone, Deserialize, PartialEq)]
+pub struct ColorSchemeVariants {
+    pub dark: ThemeBuilder,
+    pub light: ThemeBuilder,
 }
 
 impl ColorScheme {
@@ -36,19 +48,78 @@ impl ColorScheme {
         }
     }
 
-    pub fn read_theme(&self) -> Result<ThemeBuilder, Error> {
+    pub fn read_theme(&self) -> Result<(ThemeBuilder, Option<ThemeBuilder>), Error> {
         let Some(path) = self.path.as_ref() else {
             return Err(Error::ThemePathNotFound);
         };
 
         let file = std::fs::read_to_string(path)?;
-        let theme: ThemeBuilder = ron::from_str(&file)?;
-        Ok(theme)
+        Self::parse(&file)
     }
 
-    pub fn current_theme() -> ThemeBuilder {
-        let theme_mode_config = ThemeMode::config().ok();
-        let theme_mode = theme_mode_config
+    /// Parse a color scheme file, which is either a single `ThemeBuilder`
+    /// or a `ColorSchemeVariants` pair. Pairs return the dark variant first.
+    pub fn parse(ron: &str) -> Result<(ThemeBuilder, Option<ThemeBuilder>), Error> {
+        if let Ok(variants) = ron::from_str::<ColorSchemeVariants>(ron) {
+            return Ok((variants.dark, Some(variants.light)));
+        }
+
+        Ok((ron::from_str(ron)?, None))
+    }
+
+    /// Serialize the color scheme in the format `parse` reads back.
+    pub fn to_ron(&self) -> Result<String, Error> {
+        let pretty = ron::ser::PrettyConfig::default();
+        let ron = match self.variants() {
+            Some(variants) => ron::ser::to_string_pretty(&variants, pretty)?,
+            None => ron::ser::to_string_pretty(&self.theme, pretty)?,
+        };
+        Ok(ron)
+    }
+
+    /// Split the color scheme into its dark and light variants, if it has both.
+    pub fn variants(&self) -> Option<ColorSchemeVariants> {
+        let variant = self.variant.clone()?;
+        Some(if self.theme.palette.is_dark() {
+            ColorSchemeVariants {
+                dark: self.theme.clone(),
+                light: variant,
+            }
+        } else {
+            ColorSchemeVariants {
+                dark: variant,
+                light: self.theme.clone(),
+            }
+        })
+    }
+
+    /// The directory color schemes are installed to.
+    pub fn themes_dir() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("themes/cosmic"))
+    }
+
+    /// Find an installed color scheme by name.
+    pub fn find(name: &str) -> Result<Self, Error> {
+        Self::installed()
+            .into_iter()
+            .find(|color_scheme| color_scheme.name == name)
+            .ok_or_else(|| Error::NotFound("color scheme", name.to_string()))
+    }
+
+    /// Get the color scheme currently selected in the config.
+    pub fn selected() -> Self {
+        match Self::get_entry(&Self::config()) {
+            Ok(config) => config,
+            Err((errors, default)) => {
+                log::error!("Failed to load color scheme config: {errors:#?}");
+                default
+            }
+        }
+    }
+
+    pub fn theme_mode() -> ThemeMode {
+        ThemeMode::config()
+            .ok()
             .as_ref()
             .map(|c| match ThemeMode::get_entry(c) {
                 Ok(t) => t,
@@ -59,7 +130,128 @@ impl ColorScheme {
                     t
                 }
             })
-            .unwrap_or_default();
+            .unwrap_or_default()
+    }
+
+    /// Write the theme built from `builder` to the config of the active theme mode.
+    ///
+    /// When a `variant` is given both the dark and light configs are written, so
+    /// toggling the system theme mode keeps the same color scheme. With `keep_style`
+    /// the roundness, density and gaps in the configs are kept.
+    pub fn set_theme(
+        builder: &ThemeBuilder,
+        variant: Option<&ThemeBuilder>,
+        keep_style: bool,
+    ) -> Result<(), Error> {
+        let Some(variant) = variant else {
+            return Self::write_theme(builder, Self::theme_mode().is_dark, keep_style);
+        };
+
+        // Like `variants`, so a pair whose halves are in the same mode still fills both configs.
+        let (dark, light) = if builder.palette.is_dark() {
+            (builder, variant)
+        } else {
+            (variant, builder)
+        };
+        Self::write_theme(dark, true, keep_style)?;
+        Self::write_theme(light, false, keep_style)
+    }
+
+    fn write_theme(builder: &ThemeBuilder, is_dark: bool, keep_style: bool) -> Result<(), Error> {
+        let config = if is_dark {
+            Theme::dark_config()
+        } else {
+            Theme::light_config()
+        }?;
+
+        let mut theme = builder.clone().build();
+        if keep_style {
+            InterfaceStyle::read(&config).apply_to(&mut theme);
+        }
+        theme.write_entry(&config)?;
+        Ok(())
+    }
+
+    /// The dark and light themes in the system config, to write back with `restore_themes`.
+    pub fn current_themes() -> Result<(Theme, Theme), Error> {
+        let read = |config: Config| Theme::get_entry(&config).unwrap_or_else(|(_, theme)| theme);
+        Ok((read(Theme::dark_config()?), read(Theme::light_config()?)))
+    }
+
+    /// Write back the dark and light themes saved with `current_themes`.
+    pub fn restore_themes((dark, light): &(Theme, Theme)) -> Result<(), Error> {
+        dark.write_entry(&Theme::dark_config()?)?;
+        light.write_entry(&Theme::light_config()?)?;
+        Ok(())
+    }
+
+    /// Select this color scheme and write its theme to the system config, see `set_theme`.
+    pub fn apply(&self, keep_style: bool) -> Result<(), Error> {
+        let config = Self::config();
+        let mut selected = Self::selected();
+        selected.set_name(&config, self.name.clone())?;
+        selected.set_path(&config, self.path.clone())?;
+
+        let (theme, variant) = match self.path {
+            Some(_) => self.read_theme()?,
+            None => (self.theme.clone(), self.variant.clone()),
+        };
+        Self::set_theme(&theme, variant.as_ref(), keep_style)
+    }
+
+    /// Names become file names in the themes directory, so they cannot be empty,
+    /// hidden or contain path separators.
+    pub fn validate_name(name: &str) -> Result<(), Error> {
+        if name.trim().is_empty() || name.starts_with('.') || name.contains(['/', '\\', '\0']) {
+            return Err(Error::InvalidName(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Where this color scheme is installed to.
+    fn install_path(&self) -> Result<PathBuf, Error> {
+        Self::validate_name(&self.name)?;
+        let themes_dir = Self::themes_dir().ok_or(Error::ThemePathNotFound)?;
+        Ok(themes_dir.join(format!("{}.ron", self.name)))
+    }
+
+    /// Write this color scheme to the themes directory, returning the new path.
+    pub fn install(&self) -> Result<PathBuf, Error> {
+        let path = self.install_path()?;
+        if let Some(themes_dir) = path.parent() {
+            std::fs::create_dir_all(themes_dir)?;
+        }
+
+        std::fs::write(&path, self.to_ron()?)?;
+        if let Some(provenance) = &self.provenance {
+            provenance.write(&path)?;
+        }
+        Ok(path)
+    }
+
+    /// Install this color scheme under a name no other color scheme uses yet.
+    pub fn install_new(&self) -> Result<PathBuf, Error> {
+        if self.install_path()?.exists() {
+            return Err(Error::AlreadyExists("color scheme", self.name.clone()));
+        }
+        self.install()
+    }
+
+    /// Rename the file of an installed color scheme, returning the new path.
+    pub fn rename(&self, name: &str) -> Result<PathBuf, Error> {
+        Self::validate_name(name)?;
+        let path = self.path.as_ref().ok_or(Error::ThemePathNotFound)?;
+        let new_path = path.with_file_name(format!("{name}.ron"));
+        if new_path.exists() {
+            return Err(Error::AlreadyExists("color scheme", name.to_string()));
+        }
+        std::fs::rename(path, &new_path)?;
+        Provenance::rename(path, &new_path)?;
+        Ok(new_path)
+    }
+
+    pub fn current_theme() -> ThemeBuilder {
+        let theme_mode = Self::theme_mode();
         let theme_builder_config = if theme_mode.is_dark {
             ThemeBuilder::dark_config()
         } else {
@@ -87,9 +279,9 @@ impl ColorScheme {
         )
     }
 
-    pub fn installed() -> Result<Vec<Self>, Error> {
-        let mut color_schemes = vec![];
-
+    /// Every directory color schemes are read from, `XDG_DATA_DIRS` first
+    /// and the user's data directory last.
+    pub fn themes_dirs() -> Vec<PathBuf> {
         let xdg_data_home = std::env::var("XDG_DATA_HOME")
             .ok()
             .and_then(|value| {
@@ -102,7 +294,44 @@ impl ColorScheme {
             .or_else(dirs::data_local_dir)
             .map(|dir| dir.join("themes/cosmic"));
 
-        if let Some(ref xdg_data_home) = xdg_data_home {
+        let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
+
+        xdg_data_dirs
+            .as_deref()
+            .or(Some("/usr/local/share/:/usr/share/"))
+            .into_iter()
+            .flat_map(|arg| std::env::split_paths(arg).map(|dir| dir.join("themes/cosmic")))
+            .chain(xdg_data_home)
+            .collect()
+    }
+
+    /// The installed color schemes, skipping files that cannot be read.
+    pub fn installed() -> Vec<Self> {
+        let (color_schemes, problems) = Self::scan();
+        for problem in problems {
+            log::error!(
+                "skipping broken color scheme {}: {}",
+                problem.path.display(),
+                problem.error
+            );
+        }
+        color_schemes
+    }
+
+    /// Read every themes directory, collecting the files that fail to parse
+    /// instead of giving up on the first one.
+    pub fn scan() -> (Vec<Self>, Vec<BrokenFile>) {
+        Self::scan_with(Self::from_file)
+    }
+
+    /// Like `scan`, reading each file with `read`, which can skip files it has already parsed.
+    pub fn scan_with(
+        mut read: impl FnMut(&Path) -> Result<Self, Error>,
+    ) -> (Vec<Self>, Vec<BrokenFile>) {
+        let mut color_schemes = vec![];
+        let mut problems = vec![];
+
+        if let Some(xdg_data_home) = Self::themes_dirs().last() {
             if !xdg_data_home.exists() {
                 if let Err(e) = std::fs::create_dir_all(xdg_data_home) {
                     log::error!("failed to create the themes directory: {e}")
@@ -110,39 +339,43 @@ impl ColorScheme {
             }
         }
 
-        let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
-
-        let xdg_data_dirs = xdg_data_dirs
-            .as_deref()
-            .or(Some("/usr/local/share/:/usr/share/"))
-            .into_iter()
-            .flat_map(|arg| std::env::split_paths(arg).map(|dir| dir.join("themes/cosmic")));
-
-        for themes_directory in xdg_data_dirs.chain(xdg_data_home) {
+        for themes_directory in Self::themes_dirs() {
             let Ok(read_dir) = std::fs::read_dir(&themes_directory) else {
                 continue;
             };
 
             for entry in read_dir.filter_map(Result::ok) {
                 let path = entry.path();
-                let color_scheme = std::fs::read_to_string(&path)?;
-                let theme: ThemeBuilder = ron::from_str(&color_scheme)?;
-                let name = path
-                    .file_stem()
-                    .and_then(|name| name.to_str())
-                    .map(|name| name.to_string())
-                    .unwrap_or_default();
-                let color_scheme = ColorScheme {
-                    name,
-                    path: Some(path),
-                    link: None,
-                    author: None,
-                    theme,
-                };
-                color_schemes.push(color_scheme);
+                if path.is_dir() {
+                    continue;
+                }
+                match read(&path) {
+                    Ok(color_scheme) => color_schemes.push(color_scheme),
+                    Err(e) => problems.push(BrokenFile::new(path, e)),
+                }
             }
         }
 
-        Ok(color_schemes)
+        (color_schemes, problems)
+    }
+
+    /// Read an installed color scheme file and its provenance.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let (theme, variant) = Self::parse(&std::fs::read_to_string(path)?)?;
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        let provenance = Provenance::read(path);
+        Ok(ColorScheme {
+            name,
+            link: provenance.as_ref().and_then(|p| p.link.clone()),
+            author: provenance.as_ref().and_then(|p| p.author.clone()),
+            path: Some(path.to_path_buf()),
+            theme,
+            variant,
+            provenance,
+        })
     }
 }
@@ -0,0 +1,196 @@
+//! WCAG 2.x contrast checks for the text and buttons of a color scheme.
+
+use cosmic::{
+    cosmic_theme::{palette::Srgba, Theme},
+    iced::Alignment,
+    widget::{self, tooltip},
+    Apply, Element,
+};
+
+use crate::{
+    core::{
+        color::{composite, contrast_ratio},
+        style,
+    },
+    fl,
+};
+
+/// Minimum contrast ratio for normal text at level AA.
+pub const AA_TEXT: f32 = 4.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastCheck {
+    /// Identifies the pair in machine readable output, such as `dark.button-text`.
+    pub key: String,
+    /// The pair as shown to the user.
+    pub pair: String,
+    pub ratio: f32,
+    pub minimum: f32,
+}
+
+impl ContrastCheck {
+    pub fn passes(&self) -> bool {
+        self.ratio >= self.minimum
+    }
+}
+
+/// Check the text on each container, the accent text and the button text of a theme.
+pub fn checks(theme: &Theme) -> Vec<ContrastCheck> {
+    let background = theme.background.base;
+    let primary = theme.primary.base;
+    let card = composite(theme.primary.component.base, primary);
+    let button = composite(theme.button.base, background);
+    let accent_button = composite(theme.accent_button.base, background);
+    let (mode_key, mode) = if theme.is_dark {
+        ("dark", fl!("dark"))
+    } else {
+        ("light", fl!("light"))
+    };
+    let check = |key: &str, name: String, minimum: f32, foreground: Srgba, background: Srgba| {
+        ContrastCheck {
+            key: format!("{mode_key}.{key}"),
+            pair: format!("{mode}: {name}"),
+            ratio: contrast_ratio(foreground, background),
+            minimum,
+        }
+    };
+
+    vec![
+        check(
+            "background-text",
+            fl!("contrast-background-text"),
+            AA_TEXT,
+            theme.background.on,
+            background,
+        ),
+        check(
+            "primary-text",
+            fl!("contrast-primary-text"),
+            AA_TEXT,
+            theme.primary.on,
+            primary,
+        ),
+        check(
+            "secondary-text",
+            fl!("contrast-secondary-text"),
+            AA_TEXT,
+            theme.secondary.on,
+            theme.secondary.base,
+        ),
+        check(
+            "card-text",
+            fl!("contrast-card-text"),
+            AA_TEXT,
+            theme.primary.component.on,
+            card,
+        ),
+        check(
+            "accent-text",
+            fl!("contrast-accent-text"),
+            AA_TEXT,
+            theme.accent.base,
+            background,
+        ),
+        check(
+            "accent-primary-text",
+            fl!("contrast-accent-primary-text"),
+            AA_TEXT,
+            theme.accent.base,
+            primary,
+        ),
+        check(
+            "button-text",
+            fl!("contrast-button-text"),
+            AA_TEXT,
+            theme.button.on,
+            button,
+        ),
+        check(
+            "accent-button-text",
+            fl!("contrast-accent-button-text"),
+            AA_TEXT,
+            theme.accent_button.on,
+            accent_button,
+        ),
+    ]
+}
+
+/// Checks for a color scheme and, if it has one, its opposite variant.
+pub fn report(theme: &Theme, variant: Option<&Theme>) -> Vec<ContrastCheck> {
+    let mut checks = checks(theme);
+    if let Some(variant) = variant {
+        checks.extend(self::checks(variant));
+    }
+    checks
+}
+
+/// A badge for a preview card drawn with `theme`, the tooltip lists the failing
+/// pairs of `checks`.
+pub fn badge<'a>(
+    theme: &Theme,
+    checks: &[ContrastCheck],
+    spacing: &cosmic::cosmic_theme::Spacing,
+) -> Element<'a, super::Message> {
+    let failing: Vec<&ContrastCheck> = checks.iter().filter(|check| !check.passes()).collect();
+
+    let (label, component) = if failing.is_empty() {
+        ("AA".to_string(), &theme.success)
+    } else {
+        (format!("! {}", failing.len()), &theme.warning)
+    };
+
+    let details = if failing.is_empty() {
+        widget::column().push(widget::text(fl!("contrast-passes")))
+    } else {
+        failing.iter().fold(
+            widget::column().push(widget::text(fl!("contrast-fails"))),
+            |column, check| {
+                column.push(widget::text::caption(format!(
+                    "{} — {:.2}:1 / {}:1",
+                    check.pair, check.ratio, check.minimum
+                )))
+            },
+        )
+    };
+
+    widget::tooltip::tooltip(
+        widget::text::caption(label)
+            .apply(widget::container)
+            .padding([0, spacing.space_xxs])
+            .align_y(Alignment::Center)
+            .class(style::badge(component.base.into(), component.on.into())),
+        details.spacing(spacing.space_xxxs),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_not_localized() {
+        let checks = checks(&Theme::dark());
+        assert!(checks.iter().all(|check| check.key.starts_with("dark.")));
+        assert!(checks
+            .iter()
+            .all(|check| check.key.is_ascii() && !check.key.contains(' ')));
+    }
+
+    #[test]
+    fn passes_uses_the_minimum_of_the_check() {
+        let check = ContrastCheck {
+            key: "dark.button-text".into(),
+            pair: String::new(),
+            ratio: 3.5,
+            minimum: 3.0,
+        };
+        assert!(check.passes());
+        assert!(!ContrastCheck {
+            minimum: AA_TEXT,
+            ..check
+        }
+        .passes());
+    }
+}
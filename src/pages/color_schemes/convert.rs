@@ -0,0 +1,539 @@
+//! Converters that build a `ThemeBuilder` from color schemes made for other programs.
+
+use std::path::Path;
+
+use cosmic::cosmic_theme::{palette::Srgba, ThemeBuilder};
+use serde::Deserialize;
+
+use crate::{
+    core::color::{parse_hex, relative_luminance},
+    Error,
+};
+
+use super::config::ColorScheme;
+
+/// File patterns offered in the import dialog for the formats below.
+pub const PATTERNS: &[&str] = &["*.yaml", "*.yml", "*.toml", "*.conf", "*.ini", "*.json"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A COSMIC `ThemeBuilder`, or a pair of them.
+    Cosmic,
+    /// base16 or base24 YAML.
+    Base16,
+    Alacritty,
+    Kitty,
+    Foot,
+    /// pywal `colors.json`.
+    Pywal,
+}
+
+/// The colors a COSMIC theme is derived from.
+#[derive(Debug, Clone, Default)]
+struct Palette {
+    background: Option<Srgba>,
+    foreground: Option<Srgba>,
+    container: Option<Srgba>,
+    accent: Option<Srgba>,
+    destructive: Option<Srgba>,
+    warning: Option<Srgba>,
+    success: Option<Srgba>,
+}
+
+/// Background, foreground and the 16 ANSI colors of a terminal theme.
+#[derive(Debug, Clone, Default)]
+struct TerminalColors {
+    background: Option<Srgba>,
+    foreground: Option<Srgba>,
+    ansi: [Option<Srgba>; 16],
+}
+
+#[derive(Debug, Deserialize)]
+struct PywalColors {
+    special: PywalSpecial,
+    colors: std::collections::HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PywalSpecial {
+    background: String,
+    foreground: String,
+}
+
+impl Format {
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+
+        match extension.as_deref() {
+            Some("ron") => Some(Self::Cosmic),
+            Some("json") if serde_json::from_str::<PywalColors>(content).is_ok() => {
+                Some(Self::Pywal)
+            }
+            Some("json") => None,
+            _ if content.contains("base00") => Some(Self::Base16),
+            Some("toml" | "yaml" | "yml") => Some(Self::Alacritty),
+            Some("ini") => Some(Self::Foot),
+            _ if content.lines().any(|line| line.trim() == "[colors]") => Some(Self::Foot),
+            Some("conf") => Some(Self::Kitty),
+            _ if content.lines().any(|line| line.starts_with("color0 ")) => Some(Self::Kitty),
+            _ => None,
+        }
+    }
+}
+
+/// Read a color scheme in any supported format.
+pub fn import(path: &Path, content: &str) -> Result<(ThemeBuilder, Option<ThemeBuilder>), Error> {
+    let format = Format::detect(path, content).ok_or(Error::UnsupportedFormat)?;
+    let palette = match format {
+        Format::Cosmic => return ColorScheme::parse(content),
+        Format::Base16 => base16(content),
+        Format::Alacritty => alacritty(content).into(),
+        Format::Kitty => kitty(content).into(),
+        Format::Foot => foot(content).into(),
+        Format::Pywal => pywal(content)?.into(),
+    };
+    Ok((palette.build()?, None))
+}
+
+impl Palette {
+    fn build(self) -> Result<ThemeBuilder, Error> {
+        let background = self.background.ok_or(Error::UnsupportedFormat)?;
+        let mut builder = if relative_luminance(background) < 0.5 {
+            ThemeBuilder::dark()
+        } else {
+            ThemeBuilder::light()
+        };
+
+        builder.bg_color = Some(background);
+        builder.primary_container_bg = self.container;
+        builder.text_tint = self.foreground.map(|color| color.color);
+        builder.accent = self.accent.map(|color| color.color);
+        builder.destructive = self.destructive.map(|color| color.color);
+        builder.warning = self.warning.map(|color| color.color);
+        builder.success = self.success.map(|color| color.color);
+        Ok(builder)
+    }
+}
+
+impl From<TerminalColors> for Palette {
+    fn from(colors: TerminalColors) -> Self {
+        let ansi = |normal: usize| colors.ansi[normal].or(colors.ansi[normal + 8]);
+        Self {
+            background: colors.background.or(colors.ansi[0]),
+            foreground: colors.foreground.or(colors.ansi[7]),
+            container: None,
+            accent: ansi(4),
+            destructive: ansi(1),
+            warning: ansi(3),
+            success: ansi(2),
+        }
+    }
+}
+
+/// Find the first hex color in a value such as `"#1d1f21"`, `'0x1d1f21'` or `1d1f21 # comment`.
+fn find_hex(value: &str) -> Option<Srgba> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    let value = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix('#'))
+        .unwrap_or(value);
+    parse_hex(value.get(..6)?)
+}
+
+/// Split `key: value`, `key = value`, `key=value` and `key value` lines.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let index = line.find([':', '=', ' ', '\t'])?;
+    let (key, value) = line.split_at(index);
+    let value = value.trim_start();
+    let value = value.strip_prefix([':', '=']).unwrap_or(value).trim();
+    Some((key.trim(), value))
+}
+
+fn base16(content: &str) -> Palette {
+    let mut base = [None; 24];
+    for (key, value) in content.lines().filter_map(key_value) {
+        let Some(index) = key
+            .strip_prefix("base")
+            .and_then(|index| usize::from_str_radix(index, 16).ok())
+        else {
+            continue;
+        };
+        if let Some(slot) = base.get_mut(index) {
+            *slot = find_hex(value);
+        }
+    }
+
+    Palette {
+        background: base[0x00],
+        foreground: base[0x05],
+        container: base[0x01],
+        accent: base[0x0D],
+        destructive: base[0x08],
+        warning: base[0x0A],
+        success: base[0x0B],
+    }
+}
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Alacritty themes, both the TOML format and the legacy YAML one.
+fn alacritty(content: &str) -> TerminalColors {
+    let mut colors = TerminalColors::default();
+    let mut section = String::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+            section = header.rsplit('.').next().unwrap_or_default().to_string();
+            continue;
+        }
+        let Some((key, value)) = key_value(line) else {
+            continue;
+        };
+        if value.is_empty() {
+            // A YAML mapping such as `normal:` opens a new section.
+            section = key.to_string();
+            continue;
+        }
+
+        let color = find_hex(value);
+        match (section.as_str(), key) {
+            ("primary", "background") => colors.background = color,
+            ("primary", "foreground") => colors.foreground = color,
+            ("normal" | "bright", name) => {
+                if let Some(index) = ANSI_NAMES.iter().position(|n| *n == name) {
+                    let offset = if section == "bright" { 8 } else { 0 };
+                    colors.ansi[index + offset] = color;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    colors
+}
+
+fn kitty(content: &str) -> TerminalColors {
+    let mut colors = TerminalColors::default();
+    for (key, value) in content.lines().filter_map(key_value) {
+        let color = find_hex(value);
+        match key {
+            "background" => colors.background = color,
+            "foreground" => colors.foreground = color,
+            key => {
+                if let Some(index) = key
+                    .strip_prefix("color")
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .filter(|index| *index < 16)
+                {
+                    colors.ansi[index] = color;
+                }
+            }
+        }
+    }
+    colors
+}
+
+fn foot(content: &str) -> TerminalColors {
+    let mut colors = TerminalColors::default();
+    let mut in_colors = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_colors = trimmed.starts_with("[colors");
+            continue;
+        }
+        if !in_colors {
+            continue;
+        }
+        let Some((key, value)) = key_value(line) else {
+            continue;
+        };
+
+        let color = find_hex(value);
+        match key {
+            "background" => colors.background = color,
+            "foreground" => colors.foreground = color,
+            key => {
+                let (offset, index) = match key.strip_prefix("regular") {
+                    Some(index) => (0, index),
+                    None => match key.strip_prefix("bright") {
+                        Some(index) => (8, index),
+                        None => continue,
+                    },
+                };
+                if let Some(index) = index.parse::<usize>().ok().filter(|index| *index < 8) {
+                    colors.ansi[index + offset] = color;
+                }
+            }
+        }
+    }
+
+    colors
+}
+
+fn pywal(content: &str) -> Result<TerminalColors, Error> {
+    let pywal: PywalColors = serde_json::from_str(content)?;
+    let mut colors = TerminalColors {
+        background: parse_hex(&pywal.special.background),
+        foreground: parse_hex(&pywal.special.foreground),
+        ..Default::default()
+    };
+    for (index, color) in colors.ansi.iter_mut().enumerate() {
+        *color = pywal
+            .colors
+            .get(&format!("color{index}"))
+            .and_then(|hex| parse_hex(hex));
+    }
+    Ok(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE16: &str = r#"scheme: "Tomorrow Night"
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "1d1f21"
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+    const ALACRITTY: &str = r#"# Colors (Tomorrow Night)
+
+[colors.primary]
+background = '#1d1f21'
+foreground = '#c5c8c6'
+
+[colors.cursor]
+text = '#1d1f21'
+cursor = '#ffffff'
+
+[colors.normal]
+black   = '#1d1f21'
+red     = '#cc6666'
+green   = '#b5bd68'
+yellow  = '#f0c674'
+blue    = '#81a2be'
+magenta = '#b294bb'
+cyan    = '#8abeb7'
+white   = '#c5c8c6'
+
+[colors.bright]
+black   = '#666666'
+red     = '#d54e53'
+green   = '#b9ca4a'
+yellow  = '#e7c547'
+blue    = '#7aa6da'
+magenta = '#c397d8'
+cyan    = '#70c0b1'
+white   = '#eaeaea'
+"#;
+
+    const KITTY: &str = r#"# vim:ft=kitty
+
+## name: Tomorrow Night
+## author: Chris Kempson
+
+background #1d1f21
+foreground #c5c8c6
+cursor #c5c8c6
+selection_background #373b41
+selection_foreground #c5c8c6
+
+#: black
+color0 #1d1f21
+color8 #969896
+
+#: red
+color1 #cc6666
+color9 #cc6666
+
+#: green
+color2 #b5bd68
+color10 #b5bd68
+
+#: yellow
+color3 #f0c674
+color11 #f0c674
+
+#: blue
+color4 #81a2be
+color12 #81a2be
+
+#: magenta
+color5 #b294bb
+color13 #b294bb
+
+#: cyan
+color6 #8abeb7
+color14 #8abeb7
+
+#: white
+color7 #c5c8c6
+color15 #ffffff
+"#;
+
+    const FOOT: &str = r#"# -*- conf -*-
+# Tomorrow Night
+
+[cursor]
+color = 1d1f21 c5c8c6
+
+[colors]
+foreground = c5c8c6
+background = 1d1f21
+regular0 = 1d1f21
+regular1 = cc6666
+regular2 = b5bd68
+regular3 = f0c674
+regular4 = 81a2be
+regular5 = b294bb
+regular6 = 8abeb7
+regular7 = c5c8c6
+bright0 = 969896
+bright1 = cc6666
+bright2 = b5bd68
+bright3 = f0c674
+bright4 = 81a2be
+bright5 = b294bb
+bright6 = 8abeb7
+bright7 = ffffff
+"#;
+
+    const PYWAL: &str = r##"{
+    "wallpaper": "/home/user/Pictures/wallpaper.png",
+    "alpha": "100",
+
+    "special": {
+        "background": "#1d1f21",
+        "foreground": "#c5c8c6",
+        "cursor": "#c5c8c6"
+    },
+    "colors": {
+        "color0": "#1d1f21",
+        "color1": "#cc6666",
+        "color2": "#b5bd68",
+        "color3": "#f0c674",
+        "color4": "#81a2be",
+        "color5": "#b294bb",
+        "color6": "#8abeb7",
+        "color7": "#c5c8c6",
+        "color8": "#969896",
+        "color9": "#cc6666",
+        "color10": "#b5bd68",
+        "color11": "#f0c674",
+        "color12": "#81a2be",
+        "color13": "#b294bb",
+        "color14": "#8abeb7",
+        "color15": "#ffffff"
+    }
+}
+"##;
+
+    /// Every sample above is Tomorrow Night, so they all map to the same palette.
+    fn assert_tomorrow_night(palette: Palette) {
+        assert_eq!(palette.background, parse_hex("1d1f21"));
+        assert_eq!(palette.foreground, parse_hex("c5c8c6"));
+        assert_eq!(palette.accent, parse_hex("81a2be"));
+        assert_eq!(palette.destructive, parse_hex("cc6666"));
+        assert_eq!(palette.warning, parse_hex("f0c674"));
+        assert_eq!(palette.success, parse_hex("b5bd68"));
+    }
+
+    #[test]
+    fn key_value_separators() {
+        assert_eq!(
+            key_value("base00: \"1d1f21\""),
+            Some(("base00", "\"1d1f21\""))
+        );
+        assert_eq!(
+            key_value("black   = '#1d1f21'"),
+            Some(("black", "'#1d1f21'"))
+        );
+        assert_eq!(key_value("regular0=1d1f21"), Some(("regular0", "1d1f21")));
+        assert_eq!(key_value("color0\t#1d1f21"), Some(("color0", "#1d1f21")));
+        assert_eq!(key_value("normal:"), Some(("normal", "")));
+        assert_eq!(key_value("# black = '#1d1f21'"), None);
+    }
+
+    #[test]
+    fn detect_format() {
+        let detect = |name: &str, content: &str| Format::detect(Path::new(name), content);
+        assert_eq!(detect("tomorrow-night.yaml", BASE16), Some(Format::Base16));
+        assert_eq!(
+            detect("tomorrow-night.toml", ALACRITTY),
+            Some(Format::Alacritty)
+        );
+        assert_eq!(detect("tomorrow-night.conf", KITTY), Some(Format::Kitty));
+        assert_eq!(detect("tomorrow-night.ini", FOOT), Some(Format::Foot));
+        assert_eq!(detect("colors.json", PYWAL), Some(Format::Pywal));
+        assert_eq!(detect("package.json", r#"{"name": "theme"}"#), None);
+    }
+
+    #[test]
+    fn base16_yaml() {
+        let palette = base16(BASE16);
+        assert_eq!(palette.container, parse_hex("282a2e"));
+        assert_tomorrow_night(palette);
+    }
+
+    #[test]
+    fn alacritty_toml() {
+        let colors = alacritty(ALACRITTY);
+        assert_eq!(colors.ansi[12], parse_hex("7aa6da"));
+        assert_tomorrow_night(colors.into());
+    }
+
+    #[test]
+    fn kitty_conf() {
+        let colors = kitty(KITTY);
+        assert_eq!(colors.ansi[15], parse_hex("ffffff"));
+        assert_tomorrow_night(colors.into());
+    }
+
+    #[test]
+    fn foot_ini() {
+        let colors = foot(FOOT);
+        assert_eq!(colors.ansi[8], parse_hex("969896"));
+        assert_tomorrow_night(colors.into());
+    }
+
+    #[test]
+    fn pywal_json() {
+        let colors = pywal(PYWAL).unwrap();
+        assert_eq!(colors.ansi[8], parse_hex("969896"));
+        assert_tomorrow_night(colors.into());
+    }
+
+    #[test]
+    fn import_builds_a_dark_theme() {
+        let (builder, variant) = import(Path::new("tomorrow-night.toml"), ALACRITTY).unwrap();
+        assert!(builder.palette.is_dark());
+        assert_eq!(builder.bg_color, parse_hex("1d1f21"));
+        assert!(variant.is_none());
+    }
+}
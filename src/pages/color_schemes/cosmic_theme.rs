@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use super::config::ColorScheme;
+use super::{config::ColorScheme, provenance::Provenance};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CosmicTheme {
+    #[serde(default)]
+    pub id: Option<serde_json::Value>,
     pub name: String,
     pub ron: String,
     pub author: String,
@@ -12,12 +14,27 @@ pub struct CosmicTheme {
 
 impl From<CosmicTheme> for ColorScheme {
     fn from(theme: CosmicTheme) -> Self {
+        let (builder, variant) = ColorScheme::parse(&theme.ron).unwrap_or_else(|e| {
+            log::error!("failed to parse color scheme {}: {e}", theme.name);
+            Default::default()
+        });
+        let remote_id = theme.id.map(|id| match id {
+            serde_json::Value::String(id) => id,
+            id => id.to_string(),
+        });
         Self {
             name: theme.name,
             path: None,
+            provenance: Some(Provenance {
+                author: Some(theme.author.clone()),
+                link: Some(theme.link.clone()),
+                remote_id,
+                ..Default::default()
+            }),
             link: Some(theme.link),
             author: Some(theme.author),
-            theme: ron::from_str(&theme.ron).unwrap(),
+            theme: builder,
+            variant,
         }
     }
 }
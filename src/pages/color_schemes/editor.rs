@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+use cosmic::{
+    cosmic_theme::{palette::Srgba, Theme, ThemeBuilder},
+    iced::{Alignment, Color, Length},
+    widget, Apply, Element,
+};
+
+use crate::{
+    core::{
+        color::{parse_hex, to_hex},
+        style,
+    },
+    fl, Error,
+};
+
+use super::{cache::Built, config::ColorScheme, preview, vision::Deficiency};
+
+/// Edits a copy of an installed color scheme, the original file is left untouched.
+pub struct Editor {
+    pub color_scheme: ColorScheme,
+    pub theme: ThemeBuilder,
+    /// The opposite mode of `theme`, for color schemes that come in pairs.
+    pub variant: Option<ThemeBuilder>,
+    /// Whether the fields edit `variant` instead of `theme`.
+    editing_variant: bool,
+    /// The mode of `theme` followed by the mode of `variant`, for the mode dropdown.
+    mode_options: Vec<String>,
+    inputs: HashMap<ColorField, String>,
+    /// `theme` and `variant` built for the preview, rebuilt on every edit.
+    built: Built,
+    /// Color vision deficiency the preview is simulated with.
+    vision: Option<Deficiency>,
+    /// The dark and light themes before the first temporary apply, restored on close.
+    previous: Option<(Theme, Theme)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Color(ColorField, String),
+    ResetColor(ColorField),
+    Radius(Radius, f32),
+    OuterGap(u32),
+    InnerGap(u32),
+    ActiveHint(u32),
+    Frosted(bool),
+    EditVariant(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorField {
+    Accent,
+    Background,
+    PrimaryContainer,
+    NeutralTint,
+    TextTint,
+    WindowHint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radius {
+    Xs,
+    S,
+    M,
+    L,
+    Xl,
+}
+
+impl ColorField {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Accent,
+            Self::Background,
+            Self::PrimaryContainer,
+            Self::NeutralTint,
+            Self::TextTint,
+            Self::WindowHint,
+        ]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Accent => fl!("accent-color"),
+            Self::Background => fl!("background-color"),
+            Self::PrimaryContainer => fl!("primary-container-color"),
+            Self::NeutralTint => fl!("neutral-tint"),
+            Self::TextTint => fl!("text-tint"),
+            Self::WindowHint => fl!("window-hint"),
+        }
+    }
+
+    pub fn get(&self, theme: &ThemeBuilder) -> Option<Srgba> {
+        let opaque = |color: cosmic::cosmic_theme::palette::Srgb| {
+            Srgba::new(color.red, color.green, color.blue, 1.0)
+        };
+        match self {
+            Self::Accent => theme.accent.map(opaque),
+            Self::Background => theme.bg_color,
+            Self::PrimaryContainer => theme.primary_container_bg,
+            Self::NeutralTint => theme.neutral_tint.map(opaque),
+            Self::TextTint => theme.text_tint.map(opaque),
+            Self::WindowHint => theme.window_hint.map(opaque),
+        }
+    }
+
+    pub fn set(&self, theme: &mut ThemeBuilder, color: Option<Srgba>) {
+        match self {
+            Self::Accent => theme.accent = color.map(|color| color.color),
+            Self::Background => theme.bg_color = color,
+            Self::PrimaryContainer => theme.primary_container_bg = color,
+            Self::NeutralTint => theme.neutral_tint = color.map(|color| color.color),
+            Self::TextTint => theme.text_tint = color.map(|color| color.color),
+            Self::WindowHint => theme.window_hint = color.map(|color| color.color),
+        }
+    }
+}
+
+impl Radius {
+    pub fn all() -> &'static [Self] {
+        &[Self::Xs, Self::S, Self::M, Self::L, Self::Xl]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Xs => "XS",
+            Self::S => "S",
+            Self::M => "M",
+            Self::L => "L",
+            Self::Xl => "XL",
+        }
+    }
+
+    pub fn get(&self, theme: &ThemeBuilder) -> f32 {
+        let radii = &theme.corner_radii;
+        match self {
+            Self::Xs => radii.radius_xs[0],
+            Self::S => radii.radius_s[0],
+            Self::M => radii.radius_m[0],
+            Self::L => radii.radius_l[0],
+            Self::Xl => radii.radius_xl[0],
+        }
+    }
+
+    pub fn set(&self, theme: &mut ThemeBuilder, radius: f32) {
+        let radii = &mut theme.corner_radii;
+        let corners = match self {
+            Self::Xs => &mut radii.radius_xs,
+            Self::S => &mut radii.radius_s,
+            Self::M => &mut radii.radius_m,
+            Self::L => &mut radii.radius_l,
+            Self::Xl => &mut radii.radius_xl,
+        };
+        *corners = [radius; 4];
+    }
+}
+
+impl Editor {
+    pub fn new(color_scheme: ColorScheme, vision: Option<Deficiency>) -> Self {
+        let theme = color_scheme.theme.clone();
+        let variant = color_scheme.variant.clone();
+        let mode = |theme: &ThemeBuilder| match theme.palette.is_dark() {
+            true => fl!("dark"),
+            false => fl!("light"),
+        };
+        let mode_options = std::iter::once(&theme)
+            .chain(variant.as_ref())
+            .map(mode)
+            .collect();
+
+        let mut built = Built::new(&theme, variant.as_ref());
+        built.simulate(vision);
+
+        Self {
+            inputs: inputs(&theme),
+            built,
+            vision,
+            color_scheme,
+            theme,
+            variant,
+            editing_variant: false,
+            mode_options,
+            previous: None,
+        }
+    }
+
+    /// The color scheme as it would be saved right now.
+    pub fn edited(&self) -> ColorScheme {
+        ColorScheme {
+            theme: self.theme.clone(),
+            variant: self.variant.clone(),
+            ..self.color_scheme.clone()
+        }
+    }
+
+    /// The theme the fields edit, `theme` or its variant.
+    fn editing(&self) -> &ThemeBuilder {
+        match &self.variant {
+            Some(variant) if self.editing_variant => variant,
+            _ => &self.theme,
+        }
+    }
+
+    fn editing_mut(&mut self) -> &mut ThemeBuilder {
+        match &mut self.variant {
+            Some(variant) if self.editing_variant => variant,
+            _ => &mut self.theme,
+        }
+    }
+
+    /// Write the edited themes to the system config until the editor is closed.
+    pub fn apply(&mut self, keep_style: bool) -> Result<(), Error> {
+        if self.previous.is_none() {
+            self.previous = Some(ColorScheme::current_themes()?);
+        }
+        ColorScheme::set_theme(&self.theme, self.variant.as_ref(), keep_style)
+    }
+
+    /// Undo `apply`, when the edits were applied without being saved.
+    fn restore(&mut self) -> Result<(), Error> {
+        match self.previous.take() {
+            Some(previous) => ColorScheme::restore_themes(&previous),
+            None => Ok(()),
+        }
+    }
+
+    /// Keep the applied themes when the editor is dropped, once the edits are saved.
+    pub fn keep_applied(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Color(field, input) => {
+                if let Some(color) = parse_hex(&input) {
+                    field.set(self.editing_mut(), Some(color));
+                }
+                self.inputs.insert(field, input);
+            }
+            Message::ResetColor(field) => {
+                field.set(self.editing_mut(), None);
+                self.inputs.insert(field, String::new());
+            }
+            Message::Radius(radius, value) => radius.set(self.editing_mut(), value),
+            Message::OuterGap(gap) => self.editing_mut().gaps.0 = gap,
+            Message::InnerGap(gap) => self.editing_mut().gaps.1 = gap,
+            Message::ActiveHint(hint) => self.editing_mut().active_hint = hint,
+            Message::Frosted(frosted) => self.editing_mut().is_frosted = frosted,
+            Message::EditVariant(editing_variant) => {
+                self.editing_variant = editing_variant && self.variant.is_some();
+                self.inputs = inputs(self.editing());
+                return;
+            }
+        }
+        self.built = Built::new(&self.theme, self.variant.as_ref());
+        self.built.simulate(self.vision);
+    }
+
+    pub fn view<'a>(&'a self) -> Element<'a, super::Message> {
+        let spacing = cosmic::theme::spacing();
+        let edit = super::Message::Editor;
+        let theme = self.editing();
+
+        let colors = ColorField::all().iter().fold(
+            widget::settings::section().title(fl!("colors")),
+            |section, field| {
+                let field = *field;
+                let swatch = field
+                    .get(theme)
+                    .map(Color::from)
+                    .unwrap_or(Color::TRANSPARENT);
+                section.add(widget::settings::item(
+                    field.title(),
+                    widget::row()
+                        .push(
+                            widget::Space::new(24.0, 24.0)
+                                .apply(widget::container)
+                                .class(style::swatch(swatch)),
+                        )
+                        .push(
+                            widget::text_input(
+                                fl!("palette-default"),
+                                self.inputs.get(&field).map(String::as_str).unwrap_or(""),
+                            )
+                            .on_input(move |input| edit(Message::Color(field, input)))
+                            .width(120.0),
+                        )
+                        .push(
+                            widget::button::standard(fl!("reset"))
+                                .on_press(edit(Message::ResetColor(field))),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(spacing.space_xxs),
+                ))
+            },
+        );
+
+        let radii = Radius::all().iter().fold(
+            widget::settings::section().title(fl!("corner-radii")),
+            |section, radius| {
+                let radius = *radius;
+                let value = radius.get(theme);
+                // Wide enough for the round preset of the style page, and for anything rounder.
+                let max = value.max(160.0);
+                section.add(widget::settings::item(
+                    radius.title(),
+                    widget::row()
+                        .push(widget::slider(0.0..=max, value, move |value| {
+                            edit(Message::Radius(radius, value))
+                        }))
+                        .push(widget::text::text(format!("{value:.0} px")))
+                        .spacing(spacing.space_xxs),
+                ))
+            },
+        );
+
+        let layout = widget::settings::section()
+            .title(fl!("window-management"))
+            .add(widget::settings::item(
+                fl!("outer-gap"),
+                widget::row()
+                    .push(widget::slider(0..=64, theme.gaps.0, move |gap| {
+                        edit(Message::OuterGap(gap))
+                    }))
+                    .push(widget::text::text(format!("{} px", theme.gaps.0)))
+                    .spacing(spacing.space_xxs),
+            ))
+            .add(widget::settings::item(
+                fl!("inner-gap"),
+                widget::row()
+                    .push(widget::slider(0..=64, theme.gaps.1, move |gap| {
+                        edit(Message::InnerGap(gap))
+                    }))
+                    .push(widget::text::text(format!("{} px", theme.gaps.1)))
+                    .spacing(spacing.space_xxs),
+            ))
+            .add(widget::settings::item(
+                fl!("active-hint"),
+                widget::row()
+                    .push(widget::slider(0..=8, theme.active_hint, move |hint| {
+                        edit(Message::ActiveHint(hint))
+                    }))
+                    .push(widget::text::text(format!("{} px", theme.active_hint)))
+                    .spacing(spacing.space_xxs),
+            ))
+            .add(
+                widget::settings::item::builder(fl!("frosted-glass"))
+                    .toggler(theme.is_frosted, move |frosted| {
+                        edit(Message::Frosted(frosted))
+                    }),
+            );
+
+        let header = widget::row()
+            .push(widget::text::title3(fl!(
+                "edit-color-scheme-title",
+                name = self.color_scheme.name.clone()
+            )))
+            .push(widget::horizontal_space())
+            .push_maybe(self.variant.is_some().then(|| {
+                widget::dropdown(
+                    &self.mode_options,
+                    Some(usize::from(self.editing_variant)),
+                    move |index| edit(Message::EditVariant(index == 1)),
+                )
+            }))
+            .push(
+                widget::button::standard(fl!("apply-temporarily"))
+                    .on_press(super::Message::ApplyEditedColorScheme),
+            )
+            .push(
+                widget::button::suggested(fl!("save-as-new"))
+                    .on_press(super::Message::SaveEditedColorScheme(None)),
+            )
+            .push(widget::button::standard(fl!("close")).on_press(super::Message::CloseEditor))
+            .align_y(Alignment::Center)
+            .spacing(spacing.space_xxs);
+
+        widget::column()
+            .push(header)
+            .push(
+                widget::row()
+                    .push(
+                        widget::scrollable(
+                            widget::column()
+                                .push(colors)
+                                .push(radii)
+                                .push(layout)
+                                .spacing(spacing.space_s),
+                        )
+                        .width(Length::Fill),
+                    )
+                    .push(preview::preview(
+                        &self.color_scheme.name,
+                        &self.built,
+                        &spacing,
+                        280,
+                    ))
+                    .spacing(spacing.space_s),
+            )
+            .spacing(spacing.space_xs)
+            .into()
+    }
+}
+
+/// Closing the editor in any way, including leaving the page or quitting the app,
+/// undoes a temporary apply.
+impl Drop for Editor {
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            log::error!("Failed to restore the theme config: {e}");
+        }
+    }
+}
+
+/// The text of every color field, empty for fields left to the palette.
+fn inputs(theme: &ThemeBuilder) -> HashMap<ColorField, String> {
+    ColorField::all()
+        .iter()
+        .map(|field| (*field, field.get(theme).map(to_hex).unwrap_or_default()))
+        .collect()
+}
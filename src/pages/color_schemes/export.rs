@@ -0,0 +1,265 @@
+//! Writers that turn a color scheme into themes for non-COSMIC applications.
+
+use cosmic::cosmic_theme::{palette::Srgba, Theme};
+
+use crate::{
+    core::color::{composite, lightness, relative_luminance, to_hex, with_lightness},
+    fl, Error,
+};
+
+use super::config::ColorScheme;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// COSMIC RON, as stored in `themes/cosmic`.
+    #[default]
+    Cosmic,
+    /// GTK3/GTK4 `gtk.css` color definitions.
+    Gtk,
+    /// qt5ct/qt6ct color scheme.
+    Qt,
+    Alacritty,
+    Kitty,
+}
+
+impl ExportFormat {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Cosmic,
+            Self::Gtk,
+            Self::Qt,
+            Self::Alacritty,
+            Self::Kitty,
+        ]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Cosmic => fl!("cosmic-ron"),
+            Self::Gtk => "GTK (gtk.css)".into(),
+            Self::Qt => "qt5ct / qt6ct".into(),
+            Self::Alacritty => "Alacritty".into(),
+            Self::Kitty => "Kitty".into(),
+        }
+    }
+
+    /// The file name the exported color scheme is suggested to be saved as.
+    pub fn file_name(&self, name: &str) -> String {
+        match self {
+            Self::Cosmic => format!("{name}.ron"),
+            Self::Gtk => "gtk.css".into(),
+            Self::Qt => format!("{name}.conf"),
+            Self::Alacritty => format!("{name}.toml"),
+            Self::Kitty => format!("{name}.conf"),
+        }
+    }
+
+    pub fn export(&self, color_scheme: &ColorScheme) -> Result<String, Error> {
+        let theme = color_scheme.theme.clone().build();
+        Ok(match self {
+            Self::Cosmic => color_scheme.to_ron()?,
+            Self::Gtk => gtk(&color_scheme.name, &theme),
+            Self::Qt => qt(&theme),
+            Self::Alacritty => alacritty(&color_scheme.name, &theme),
+            Self::Kitty => kitty(&color_scheme.name, &theme),
+        })
+    }
+}
+
+fn hex(color: Srgba) -> String {
+    to_hex(Srgba::new(color.red, color.green, color.blue, 1.0))
+}
+
+/// Qt expects `#aarrggbb`.
+fn argb(color: Srgba) -> String {
+    let rgb = hex(color);
+    let alpha = (color.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{alpha:02x}{}", &rgb[1..])
+}
+
+fn gtk(name: &str, theme: &Theme) -> String {
+    let insensitive = &theme.background.component;
+    let colors = [
+        // GTK3 and its themes.
+        ("theme_bg_color", theme.background.base),
+        ("theme_fg_color", theme.background.on),
+        ("theme_base_color", theme.primary.base),
+        ("theme_text_color", theme.primary.on),
+        ("theme_selected_bg_color", theme.accent.base),
+        ("theme_selected_fg_color", theme.accent.on),
+        ("insensitive_bg_color", insensitive.disabled),
+        ("insensitive_fg_color", insensitive.on_disabled),
+        ("insensitive_base_color", theme.primary.base),
+        ("theme_unfocused_bg_color", theme.background.base),
+        ("theme_unfocused_fg_color", theme.background.on),
+        ("theme_unfocused_base_color", theme.primary.base),
+        ("theme_unfocused_text_color", theme.primary.on),
+        ("theme_unfocused_selected_bg_color", theme.accent.base),
+        ("theme_unfocused_selected_fg_color", theme.accent.on),
+        ("unfocused_borders", theme.background.divider),
+        // libadwaita and GTK4.
+        ("accent_color", theme.accent.base),
+        ("accent_bg_color", theme.accent.base),
+        ("accent_fg_color", theme.accent.on),
+        ("destructive_color", theme.destructive.base),
+        ("destructive_bg_color", theme.destructive.base),
+        ("destructive_fg_color", theme.destructive.on),
+        ("error_color", theme.destructive.base),
+        ("error_bg_color", theme.destructive.base),
+        ("error_fg_color", theme.destructive.on),
+        ("success_color", theme.success.base),
+        ("success_bg_color", theme.success.base),
+        ("success_fg_color", theme.success.on),
+        ("warning_color", theme.warning.base),
+        ("warning_bg_color", theme.warning.base),
+        ("warning_fg_color", theme.warning.on),
+        ("window_bg_color", theme.background.base),
+        ("window_fg_color", theme.background.on),
+        ("view_bg_color", theme.primary.base),
+        ("view_fg_color", theme.primary.on),
+        ("headerbar_bg_color", theme.background.base),
+        ("headerbar_fg_color", theme.background.on),
+        ("headerbar_border_color", theme.background.divider),
+        ("headerbar_backdrop_color", theme.background.base),
+        ("sidebar_bg_color", theme.primary.base),
+        ("sidebar_fg_color", theme.primary.on),
+        ("card_bg_color", theme.primary.base),
+        ("card_fg_color", theme.primary.on),
+        ("dialog_bg_color", theme.primary.base),
+        ("dialog_fg_color", theme.primary.on),
+        ("popover_bg_color", theme.primary.base),
+        ("popover_fg_color", theme.primary.on),
+        ("borders", theme.background.divider),
+    ];
+
+    // GTK3 CSS has no 8-digit hex, so translucent colors such as dividers are
+    // blended over the window background first.
+    colors.iter().fold(
+        format!("/* {name}, exported from COSMIC Tweaks */\n"),
+        |css, (key, color)| {
+            let color = composite(*color, theme.background.base);
+            css + &format!("@define-color {key} {};\n", hex(color))
+        },
+    )
+}
+
+fn qt(theme: &Theme) -> String {
+    let background = &theme.background;
+    let button = &background.component;
+    let shadow = Srgba::new(0.0, 0.0, 0.0, 1.0);
+
+    // Roles in QPalette::ColorRole order, as qt5ct and qt6ct expect them.
+    let active = [
+        background.on,               // WindowText
+        button.base,                 // Button
+        button.hover,                // Light
+        button.hover,                // Midlight
+        button.pressed,              // Dark
+        button.divider,              // Mid
+        theme.primary.on,            // Text
+        theme.accent.on,             // BrightText
+        button.on,                   // ButtonText
+        theme.primary.base,          // Base
+        background.base,             // Window
+        shadow,                      // Shadow
+        theme.accent.base,           // Highlight
+        theme.accent.on,             // HighlightedText
+        theme.accent.base,           // Link
+        theme.palette.accent_purple, // LinkVisited
+        theme.secondary.base,        // AlternateBase
+        background.base,             // NoRole
+        theme.primary.base,          // ToolTipBase
+        theme.primary.on,            // ToolTipText
+        button.on_disabled,          // PlaceholderText
+    ];
+    let mut disabled = active;
+    for role in [0, 6, 8, 13, 19] {
+        disabled[role] = button.on_disabled;
+    }
+
+    // Blended over the window background, instead of whatever Qt draws underneath.
+    let row = |colors: &[Srgba]| {
+        colors
+            .iter()
+            .map(|color| argb(composite(*color, background.base)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!(
+        "[ColorScheme]\nactive_colors={}\ndisabled_colors={}\ninactive_colors={}\n",
+        row(&active),
+        row(&disabled),
+        row(&active),
+    )
+}
+
+/// The 16 ANSI colors, normal then bright.
+fn ansi(theme: &Theme) -> [Srgba; 16] {
+    let (black, white) =
+        if relative_luminance(theme.background.base) < relative_luminance(theme.background.on) {
+            (theme.background.base, theme.background.on)
+        } else {
+            (theme.background.on, theme.background.base)
+        };
+    let normal = [
+        black,
+        theme.destructive.base,
+        theme.success.base,
+        theme.warning.base,
+        theme.accent.base,
+        theme.palette.accent_pink,
+        theme.palette.ext_blue,
+        white,
+    ];
+
+    let mut colors = [black; 16];
+    colors[..8].copy_from_slice(&normal);
+    for (bright, normal) in colors[8..].iter_mut().zip(normal) {
+        *bright = with_lightness(normal, lightness(normal) + BRIGHTER);
+    }
+    colors[8] = theme.palette.neutral_5;
+    colors
+}
+
+/// How much lighter, in OKLCH lightness, bright ANSI colors are than the normal ones.
+const BRIGHTER: f32 = 0.1;
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn alacritty(name: &str, theme: &Theme) -> String {
+    let colors = ansi(theme);
+    let mut toml = format!(
+        "# {name}, exported from COSMIC Tweaks\n\n[colors.primary]\nbackground = \"{}\"\nforeground = \"{}\"\n",
+        hex(theme.background.base),
+        hex(theme.background.on),
+    );
+
+    for (section, colors) in [("normal", &colors[..8]), ("bright", &colors[8..])] {
+        toml.push_str(&format!("\n[colors.{section}]\n"));
+        for (name, color) in ANSI_NAMES.iter().zip(colors) {
+            toml.push_str(&format!("{name} = \"{}\"\n", hex(*color)));
+        }
+    }
+
+    toml
+}
+
+fn kitty(name: &str, theme: &Theme) -> String {
+    let mut conf = format!(
+        "# {name}, exported from COSMIC Tweaks\n\nbackground {}\nforeground {}\ncursor {}\nselection_background {}\nselection_foreground {}\n\n",
+        hex(theme.background.base),
+        hex(theme.background.on),
+        hex(theme.accent.base),
+        hex(theme.accent.base),
+        hex(theme.accent.on),
+    );
+
+    for (index, color) in ansi(theme).iter().enumerate() {
+        conf.push_str(&format!("color{index} {}\n", hex(*color)));
+    }
+
+    conf
+}
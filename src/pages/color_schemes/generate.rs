@@ -0,0 +1,110 @@
+//! Generates light and dark color schemes from the palette of an image.
+
+use std::path::{Path, PathBuf};
+
+use color_quant::NeuQuant;
+use cosmic::cosmic_theme::{
+    palette::{FromColor, Hsl, Srgb, Srgba},
+    ThemeBuilder,
+};
+
+use crate::Error;
+
+/// Number of colors the image is reduced to before picking the accent and background.
+const PALETTE_SIZE: usize = 8;
+
+/// Generate a dark and a light color scheme from an image.
+pub async fn from_image(path: PathBuf) -> Result<(ThemeBuilder, ThemeBuilder), Error> {
+    tokio::task::spawn_blocking(move || generate(&path))
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+}
+
+/// Generate a dark and a light color scheme from the wallpaper configured in cosmic-bg.
+pub async fn from_wallpaper() -> Result<(ThemeBuilder, ThemeBuilder), Error> {
+    from_image(wallpaper()?).await
+}
+
+/// The wallpaper cosmic-bg shows on all outputs.
+///
+/// cosmic-bg stores its config as RON, only the `Path("...")` source is needed here
+/// so the entry is searched rather than deserialized. Slideshows point at a directory,
+/// the first image in it is used.
+pub fn wallpaper() -> Result<PathBuf, Error> {
+    let entry = dirs::config_dir()
+        .ok_or(Error::WallpaperNotFound)?
+        .join("cosmic/com.system76.CosmicBackground/v1/all");
+    let entry = std::fs::read_to_string(entry)?;
+
+    let path = entry
+        .split_once("Path(\"")
+        .and_then(|(_, rest)| rest.split_once("\")"))
+        .map(|(path, _)| PathBuf::from(path))
+        .ok_or(Error::WallpaperNotFound)?;
+
+    if !path.is_dir() {
+        return Ok(path);
+    }
+
+    let mut images: Vec<PathBuf> = std::fs::read_dir(&path)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| image::ImageFormat::from_path(path).is_ok())
+        .collect();
+    images.sort();
+    images.into_iter().next().ok_or(Error::WallpaperNotFound)
+}
+
+fn generate(path: &Path) -> Result<(ThemeBuilder, ThemeBuilder), Error> {
+    let image = image::open(path)?.thumbnail(128, 128).to_rgba8();
+    let pixels = image.as_raw();
+
+    let quant = NeuQuant::new(10, PALETTE_SIZE, pixels);
+    let mut population = [0usize; PALETTE_SIZE];
+    for pixel in pixels.chunks_exact(4) {
+        population[quant.index_of(pixel)] += 1;
+    }
+
+    let colors: Vec<(Hsl, usize)> = quant
+        .color_map_rgb()
+        .chunks_exact(3)
+        .zip(population)
+        .map(|(rgb, count)| {
+            let rgb = Srgb::new(rgb[0], rgb[1], rgb[2]).into_format::<f32>();
+            (Hsl::from_color(rgb), count)
+        })
+        .collect();
+
+    let dominant = colors
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(color, _)| *color)
+        .unwrap_or_default();
+
+    // Prefer colorful colors that are neither too dark nor too bright, weighted by how
+    // much of the image they cover.
+    let accent = colors
+        .iter()
+        .filter(|(color, _)| (0.25..=0.75).contains(&color.lightness))
+        .max_by(|(a, a_count), (b, b_count)| {
+            let score = |color: &Hsl, count: usize| color.saturation * (count as f32).sqrt();
+            score(a, *a_count).total_cmp(&score(b, *b_count))
+        })
+        .filter(|(color, _)| color.saturation > 0.2)
+        .map(|(color, _)| Srgb::from_color(*color));
+
+    let background = |lightness: f32, saturation: f32| {
+        let color = Hsl::new(dominant.hue, dominant.saturation.min(saturation), lightness);
+        let color = Srgb::from_color(color);
+        Srgba::new(color.red, color.green, color.blue, 1.0)
+    };
+
+    let mut dark = ThemeBuilder::dark();
+    dark.bg_color = Some(background(0.12, 0.3));
+    dark.accent = accent;
+
+    let mut light = ThemeBuilder::light();
+    light.bg_color = Some(background(0.92, 0.25));
+    light.accent = accent;
+
+    Ok((dark, light))
+}
@@ -0,0 +1,300 @@
+//! The install pipeline shared by every way of importing color schemes: the file
+//! chooser, files dropped on the window, URLs and text pasted from the clipboard.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use cosmic::{
+    iced::{Alignment, Length},
+    widget::{self, tooltip},
+    Apply, Element,
+};
+use reqwest::Url;
+
+use crate::{core::icons, fl, Error};
+
+use super::{config::ColorScheme, convert};
+
+/// How long downloading a color scheme, or an index of them, from a URL may take.
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+/// Color schemes are a few kilobytes, anything much larger is not one.
+const MAX_DOWNLOAD: usize = 1024 * 1024;
+
+/// Where a color scheme is imported from.
+#[derive(Debug, Clone)]
+pub enum Source {
+    File(PathBuf),
+    /// An `https://` or `file://` URL.
+    Url(String),
+    /// RON text pasted from the clipboard.
+    Text(String),
+}
+
+/// What happened to one imported color scheme.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Installed(ColorScheme),
+    /// A color scheme with the same name is already installed, this one waits
+    /// until it replaces it or is installed under another name.
+    Collision(ColorScheme),
+    Failed {
+        source: String,
+        error: String,
+    },
+}
+
+/// How the user resolved an outcome in the import results.
+#[derive(Debug, Clone, Copy)]
+pub enum Resolution {
+    Replace,
+    KeepBoth,
+    Dismiss,
+}
+
+impl Source {
+    /// A source for a URI chosen in the file chooser.
+    pub fn from_uri(uri: &Url) -> Self {
+        match uri.to_file_path() {
+            Ok(path) if uri.scheme() == "file" => Self::File(path),
+            _ => Self::Url(uri.to_string()),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::File(path) => path.display().to_string(),
+            Self::Url(url) => url.clone(),
+            Self::Text(_) => fl!("clipboard"),
+        }
+    }
+
+    /// Turn `file://` URLs into files, and reject URLs that are neither those nor `https://`.
+    fn validate(self) -> Result<Self, Error> {
+        let Self::Url(url) = self else {
+            return Ok(self);
+        };
+        let parsed = Url::parse(&url).map_err(|_| Error::UnsupportedUrl(url.clone()))?;
+        match parsed.scheme() {
+            "file" => parsed
+                .to_file_path()
+                .map(Self::File)
+                .map_err(|_| Error::UnsupportedUrl(url)),
+            "https" => Ok(Self::Url(url)),
+            _ => Err(Error::UnsupportedUrl(url)),
+        }
+    }
+
+    /// Read and parse the color scheme, named after the file it came from.
+    async fn read(self) -> Result<ColorScheme, Error> {
+        let (name, (theme, variant)) = match self.validate()? {
+            Self::File(path) => {
+                let content = tokio::fs::read_to_string(&path).await?;
+                (file_stem(&path)?, convert::import(&path, &content)?)
+            }
+            Self::Url(url) => {
+                let response = reqwest::Client::builder()
+                    .timeout(TIMEOUT)
+                    .build()?
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                // Detect the format from the last segment of the URL, like a file name.
+                let path = PathBuf::from(
+                    response
+                        .url()
+                        .path_segments()
+                        .and_then(|mut segments| segments.next_back())
+                        .unwrap_or_default(),
+                );
+                let content = read_capped(response, MAX_DOWNLOAD).await?;
+                (file_stem(&path)?, convert::import(&path, &content)?)
+            }
+            Self::Text(text) => (fl!("pasted-color-scheme"), ColorScheme::parse(&text)?),
+        };
+
+        Ok(ColorScheme {
+            name,
+            path: None,
+            link: None,
+            author: None,
+            theme,
+            variant,
+            provenance: None,
+        })
+    }
+}
+
+/// Read the body of a response as text, giving up once it is larger than `max` bytes.
+pub async fn read_capped(mut response: reqwest::Response, max: usize) -> Result<String, Error> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > max as u64)
+    {
+        return Err(Error::TooLarge(max));
+    }
+    let mut body = vec![];
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max {
+            return Err(Error::TooLarge(max));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+fn file_stem(path: &Path) -> Result<String, Error> {
+    path.file_stem()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .ok_or(Error::ThemePathNotFound)
+}
+
+/// Read, validate and install every source, one outcome each.
+pub async fn import(sources: Vec<Source>) -> Vec<Outcome> {
+    let mut outcomes = vec![];
+    for source in sources {
+        let label = source.label();
+        outcomes.push(match source.read().await {
+            // Installing writes to the themes directory, off the async runtime.
+            Ok(color_scheme) => tokio::task::spawn_blocking(move || install(color_scheme))
+                .await
+                .unwrap_or_else(|e| Outcome::Failed {
+                    source: label,
+                    error: e.to_string(),
+                }),
+            Err(e) => Outcome::Failed {
+                source: label,
+                error: describe(e),
+            },
+        });
+    }
+    outcomes
+}
+
+/// Install a parsed color scheme unless its name is taken.
+pub fn install(mut color_scheme: ColorScheme) -> Outcome {
+    match color_scheme.install_new() {
+        Ok(path) => {
+            color_scheme.path = Some(path);
+            Outcome::Installed(color_scheme)
+        }
+        Err(Error::AlreadyExists(..)) => Outcome::Collision(color_scheme),
+        Err(e) => Outcome::Failed {
+            source: color_scheme.name,
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Resolve a name collision by overwriting the installed color scheme.
+pub fn replace(mut color_scheme: ColorScheme) -> Outcome {
+    match color_scheme.install() {
+        Ok(path) => {
+            color_scheme.path = Some(path);
+            Outcome::Installed(color_scheme)
+        }
+        Err(e) => Outcome::Failed {
+            source: color_scheme.name,
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Resolve a name collision by installing under the first free name.
+pub fn keep_both(mut color_scheme: ColorScheme) -> Outcome {
+    color_scheme.name = free_name(&color_scheme.name);
+    install(color_scheme)
+}
+
+/// `name` followed by the first number no installed color scheme uses.
+fn free_name(name: &str) -> String {
+    let taken = |name: &str| {
+        ColorScheme::themes_dir().is_some_and(|dir| dir.join(name).with_extension("ron").exists())
+    };
+    (2..)
+        .map(|number| format!("{name} {number}"))
+        .find(|name| !taken(name))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// A parse error with the line and column it stopped at.
+fn describe(error: Error) -> String {
+    match error {
+        Error::Ron(e) => fl!(
+            "problem-at",
+            line = e.position.line,
+            column = e.position.col,
+            error = e.code.to_string()
+        ),
+        e => e.to_string(),
+    }
+}
+
+pub fn view<'a>(outcomes: &'a [Outcome]) -> Element<'a, super::Message> {
+    let spacing = cosmic::theme::spacing();
+    let resolve = |index, resolution| super::Message::ResolveImport(index, resolution);
+    outcomes
+        .iter()
+        .enumerate()
+        .fold(
+            widget::settings::section().title(fl!("import-results")),
+            |section, (index, outcome)| {
+                let (title, description) = match outcome {
+                    Outcome::Installed(color_scheme) => {
+                        (color_scheme.name.clone(), fl!("import-installed"))
+                    }
+                    Outcome::Collision(color_scheme) => {
+                        (color_scheme.name.clone(), fl!("import-collision"))
+                    }
+                    Outcome::Failed { source, error } => (source.clone(), error.clone()),
+                };
+                let mut row = widget::row().push(
+                    widget::column()
+                        .push(widget::text(title))
+                        .push(widget::text::caption(description))
+                        .width(Length::Fill),
+                );
+                match outcome {
+                    Outcome::Installed(color_scheme) => {
+                        row = row.push(widget::tooltip::tooltip(
+                            icons::get_handle("selection-mode-symbolic", 14)
+                                .apply(widget::button::icon)
+                                .padding(spacing.space_xxs)
+                                .on_press(super::Message::SetColorScheme(color_scheme.clone())),
+                            widget::text(fl!("set-color-scheme")),
+                            tooltip::Position::Bottom,
+                        ))
+                    }
+                    Outcome::Collision(_) => {
+                        row = row
+                            .push(
+                                widget::button::standard(fl!("replace"))
+                                    .on_press(resolve(index, Resolution::Replace)),
+                            )
+                            .push(
+                                widget::button::standard(fl!("keep-both"))
+                                    .on_press(resolve(index, Resolution::KeepBoth)),
+                            )
+                    }
+                    Outcome::Failed { .. } => (),
+                }
+                section.add(
+                    row.push(widget::tooltip::tooltip(
+                        icons::get_handle("window-close-symbolic", 14)
+                            .apply(widget::button::icon)
+                            .padding(spacing.space_xxs)
+                            .on_press(resolve(index, Resolution::Dismiss)),
+                        widget::text(fl!("dismiss")),
+                        tooltip::Position::Bottom,
+                    ))
+                    .align_y(Alignment::Center)
+                    .spacing(spacing.space_xxs),
+                )
+            },
+        )
+        .into()
+}
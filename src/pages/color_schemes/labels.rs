@@ -0,0 +1,100 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use cosmic::Application;
+use serde::{Deserialize, Serialize};
+
+use crate::{app::App, Error};
+
+/// Favorites and tags the user gave installed color schemes, keyed by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Labels {
+    pub favorites: BTreeSet<String>,
+    pub tags: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Labels {
+    fn path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join(App::APP_ID).join("labels.ron"))
+    }
+
+    pub fn load() -> Self {
+        let Some(labels) = Self::path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+        ron::from_str(&labels).unwrap_or_else(|e| {
+            log::error!("failed to read the color scheme labels: {e}");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Self::path().ok_or(Error::DataDirNotFound)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, ron)?;
+        Ok(())
+    }
+
+    pub fn is_favorite(&self, name: &str) -> bool {
+        self.favorites.contains(name)
+    }
+
+    pub fn toggle_favorite(&mut self, name: &str) {
+        if !self.favorites.remove(name) {
+            self.favorites.insert(name.to_string());
+        }
+    }
+
+    pub fn tags(&self, name: &str) -> Vec<String> {
+        self.tags
+            .get(name)
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn set_tags(&mut self, name: &str, tags: Vec<String>) {
+        let tags: BTreeSet<String> = tags
+            .into_iter()
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.is_empty() {
+            self.tags.remove(name);
+        } else {
+            self.tags.insert(name.to_string(), tags);
+        }
+    }
+
+    /// Every tag in use, sorted.
+    pub fn all_tags(&self) -> BTreeSet<String> {
+        self.tags.values().flatten().cloned().collect()
+    }
+
+    /// Keep the labels of a color scheme that was renamed.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if self.favorites.remove(from) {
+            self.favorites.insert(to.to_string());
+        }
+        if let Some(tags) = self.tags.remove(from) {
+            self.tags.insert(to.to_string(), tags);
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.favorites.remove(name);
+        self.tags.remove(name);
+    }
+}
+
+/// Split the comma separated tags typed in the properties dialog.
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
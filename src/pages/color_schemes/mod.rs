@@ -1,32 +1,112 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
 
-use self::config::ColorScheme;
+use self::{
+    cache::{Built, Cache, Installed},
+    catalogue::{CataloguePage, Origin, PageKey},
+    config::ColorScheme,
+    contrast::ContrastCheck,
+    labels::Labels,
+    problems::BrokenFile,
+    provenance::Provenance,
+    vision::Deficiency,
+};
 use crate::{core::grid::GridMetrics, fl};
 use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+use chrono::{DateTime, Local, Utc};
 use cosmic::{
-    cosmic_config::CosmicConfigEntry,
-    cosmic_theme::{Theme, ThemeBuilder, ThemeMode},
+    cosmic_theme::ThemeBuilder,
     widget::{
         self,
         segmented_button::{self, SingleSelect},
     },
-    Element, Task,
+    Apply, Element, Task,
 };
-use cosmic_theme::CosmicTheme;
+use query::{ModeFilter, Query, SortOrder};
 
+pub mod cache;
+pub mod catalogue;
+pub mod compare;
 pub mod config;
+pub mod contrast;
+pub mod convert;
 pub mod cosmic_theme;
+pub mod editor;
+pub mod export;
+pub mod generate;
+pub mod import;
+pub mod labels;
+pub mod opposite;
+pub mod pack;
 pub mod preview;
+pub mod problems;
+pub mod provenance;
+pub mod provider;
+pub mod query;
+pub mod render;
+pub mod vision;
+pub mod watcher;
+
+pub use provider::ColorSchemeProvider;
 
 pub struct ColorSchemes {
     installed: Vec<ColorScheme>,
-    available: Vec<ColorScheme>,
+    installed_names: Vec<String>,
+    /// Built themes of the installed color schemes, keyed by path.
+    built: HashMap<PathBuf, Built>,
+    cache: Cache,
+    installed_status: Status,
+    /// Incremented on every reload, so an older scan finishing last is discarded.
+    reloads: usize,
+    /// A scheduled color scheme requested before the installed ones finished loading.
+    scheduled: Option<String>,
+    labels: Labels,
+    /// Only show installed color schemes with this tag.
+    tag_filter: Option<String>,
+    favorites_first: bool,
+    /// "All" followed by every tag, for the tag filter dropdown.
+    tag_options: Vec<String>,
+    /// Color vision deficiency the preview cards are simulated with.
+    vision: Option<Deficiency>,
+    /// Normal vision followed by every deficiency, for the vision dropdown.
+    vision_options: Vec<String>,
+    /// Files in the themes directories that could not be read as color schemes.
+    problems: Vec<BrokenFile>,
+    /// Results of the imports since the page was opened, until they are dismissed.
+    imports: Vec<import::Outcome>,
+    /// A generated pair and the contrast checks its generated mode fails, until dismissed.
+    opposite_contrast: Option<(String, Vec<ContrastCheck>)>,
+    /// Names of the installed color schemes selected for a pack, while selecting.
+    pack_selection: Option<BTreeSet<String>>,
+    /// Newer versions of installed color schemes, and those that cannot be checked,
+    /// keyed by the installed name.
+    updates: HashMap<String, provider::Update>,
+    /// Pages of available color schemes, keyed by offset.
+    available: BTreeMap<usize, AvailablePage>,
+    /// Why the last fetch failed, cleared once a page is fetched again.
+    fetch_error: Option<String>,
     color_scheme: ColorScheme,
+    editor: Option<editor::Editor>,
+    compare: Option<compare::Compare>,
+    /// Dark and light variants generated from an image, waiting for a name.
+    generated: Option<(ThemeBuilder, ThemeBuilder)>,
     pub theme_builder: ThemeBuilder,
+    /// Keep the interface style when applying color schemes, set by the app from its config.
+    pub keep_style: bool,
     pub model: segmented_button::Model<SingleSelect>,
     pub status: Status,
     pub limit: usize,
     offset: usize,
+    query: Query,
+    provider: ColorSchemeProvider,
+    /// Incremented whenever the query changes, so stale pages are discarded.
+    generation: usize,
+    sort_options: Vec<String>,
+    mode_options: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -36,6 +116,13 @@ pub enum Status {
     LoadingMore,
 }
 
+/// A page of available color schemes, with its themes built.
+struct AvailablePage {
+    origin: Origin,
+    fetched_at: DateTime<Utc>,
+    color_schemes: Vec<(ColorScheme, Built)>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
     Installed,
@@ -44,24 +131,48 @@ pub enum Tab {
 
 impl Default for ColorSchemes {
     fn default() -> Self {
+        let labels = Labels::load();
         Self {
-            installed: ColorScheme::installed().unwrap_or_default(),
-            available: vec![],
-            color_scheme: match ColorScheme::get_entry(&ColorScheme::config()) {
-                Ok(config) => config,
-                Err((errors, default)) => {
-                    log::error!("Failed to load color scheme config: {errors:#?}");
-                    default
-                }
-            },
+            tag_options: tag_options(&labels),
+            labels,
+            tag_filter: None,
+            favorites_first: false,
+            vision: None,
+            vision_options: std::iter::once(fl!("normal-vision"))
+                .chain(Deficiency::all().iter().map(Deficiency::title))
+                .collect(),
+            installed: vec![],
+            installed_names: vec![],
+            built: HashMap::new(),
+            cache: Cache::default(),
+            installed_status: Status::Loading,
+            reloads: 0,
+            scheduled: None,
+            problems: vec![],
+            imports: vec![],
+            opposite_contrast: None,
+            pack_selection: None,
+            updates: HashMap::new(),
+            available: BTreeMap::new(),
+            fetch_error: None,
+            color_scheme: ColorScheme::selected(),
+            editor: None,
+            compare: None,
+            generated: None,
             model: segmented_button::Model::builder()
                 .insert(|b| b.text("Installed").data(Tab::Installed).activate())
                 .insert(|b| b.text("Available").data(Tab::Available))
                 .build(),
             theme_builder: ColorScheme::current_theme(),
+            keep_style: false,
             status: Status::Idle,
             limit: 15,
             offset: 0,
+            query: Query::default(),
+            provider: ColorSchemeProvider::default(),
+            generation: 0,
+            sort_options: SortOrder::all().iter().map(SortOrder::title).collect(),
+            mode_options: ModeFilter::all().iter().map(ModeFilter::title).collect(),
         }
     }
 }
@@ -71,22 +182,88 @@ pub enum Message {
     StartImport,
     ImportError,
     ImportFile(Arc<SelectedFiles>),
-    ImportSuccess(Box<ThemeBuilder>),
+    /// Files dropped on the window.
+    ImportFiles(Vec<PathBuf>),
+    ImportUrl(Option<String>),
+    PasteColorScheme,
+    ImportText(Option<String>),
+    /// Install the sources, and apply the color scheme if only one is installed and the flag is set.
+    Import(Vec<import::Source>, bool),
+    Imported(Vec<import::Outcome>, bool),
+    ResolveImport(usize, import::Resolution),
+    SelectForPack(bool),
+    ToggleInPack(String),
+    ExportPack,
+    /// The selected color schemes were written to a pack, ending the selection.
+    PackExported(String),
+    ImportPack(PathBuf),
+    PackRead(Vec<ColorScheme>),
+    /// Color schemes of a pack whose names are already installed, resolved in a dialog.
+    PackConflicts(Vec<ColorScheme>),
+    ResolvePackConflicts(Vec<(ColorScheme, pack::Conflict)>),
+    ImportSuccess(Box<ThemeBuilder>, Option<Box<ThemeBuilder>>),
     SaveCurrentColorScheme(Option<String>),
     SetColorScheme(ColorScheme),
     DeleteColorScheme(ColorScheme),
+    EditProperties(ColorScheme),
+    SaveProperties(ColorScheme, String, Vec<String>),
+    DuplicateColorScheme(ColorScheme, String, Vec<String>),
+    GenerateOpposite(ColorScheme),
+    DismissOppositeContrast,
+    ToggleFavorite(String),
+    TagFilterSelected(usize),
+    FavoritesFirst(bool),
+    VisionSelected(usize),
     InstallColorScheme(ColorScheme),
+    CheckForUpdates,
+    SetUpdates(Vec<(String, provider::Update)>),
+    UpdateColorScheme(String),
     FetchAvailableColorSchemes(ColorSchemeProvider, usize),
-    SetAvailableColorSchemes(Vec<ColorScheme>),
+    SetAvailableColorSchemes(usize, usize, Origin, Box<CataloguePage>),
+    FetchAvailableError(usize, usize, String),
+    RetryFetch,
+    Search(String),
+    SubmitSearch,
+    ClearSearch,
+    SortSelected(usize),
+    ModeFilterSelected(usize),
+    SelectProvider(ColorSchemeProvider),
     OpenContainingFolder(ColorScheme),
     OpenLink(Option<String>),
     ReloadColorSchemes,
+    SetInstalledColorSchemes(usize, Box<Installed>),
+    OpenBrokenFile(PathBuf),
+    DeleteBrokenFile(PathBuf),
+    QuarantineBrokenFile(PathBuf),
+    ScheduledColorScheme(String),
     TabSelected(segmented_button::Entity),
-}
-
-#[derive(Debug, Clone)]
-pub enum ColorSchemeProvider {
-    CosmicThemes,
+    EditColorScheme(ColorScheme),
+    Editor(editor::Message),
+    ApplyEditedColorScheme,
+    SaveEditedColorScheme(Option<String>),
+    CloseEditor,
+    OpenCompare,
+    Compare(compare::Message),
+    CloseCompare,
+    ExportColorScheme(ColorScheme, Option<export::ExportFormat>),
+    ExportPreviewImage(ColorScheme),
+    StartGenerate,
+    GenerateFromImage(Arc<SelectedFiles>),
+    GenerateFromWallpaper,
+    Generated(Box<ThemeBuilder>, Box<ThemeBuilder>),
+    GenerateError(String),
+    SaveGeneratedColorScheme(Option<String>),
+    ExportSuccess(String),
+    /// The save dialog of an export was closed without choosing a file.
+    ExportCancelled,
+    ExportError(String),
+    /// The action of the open dialog succeeded, handled by the app.
+    CloseDialog,
+    /// The action of the open dialog failed, shown in the dialog by the app.
+    DialogError(String),
+    /// An installed color scheme was renamed from the first name to the second,
+    /// handled by the app to keep the schedule pointing at it.
+    Renamed(String, String),
 }
 
 impl ColorSchemes {
@@ -100,7 +277,19 @@ impl ColorSchemes {
                 async {
                     SelectedFiles::open_file()
                         .modal(true)
+                        .multiple(true)
                         .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                        .filter(FileFilter::glob(
+                            FileFilter::new(&fl!("color-scheme-packs")),
+                            "*.zip",
+                        ))
+                        .filter(
+                            convert::PATTERNS
+                                .iter()
+                                .fold(FileFilter::new(&fl!("other-formats")), |filter, pattern| {
+                                    filter.glob(pattern)
+                                }),
+                        )
                         .send()
                         .await?
                         .response()
@@ -116,82 +305,229 @@ impl ColorSchemes {
                 },
             )),
             Message::ImportError => log::error!("failed to import a custom theme."),
+            Message::ExportColorScheme(color_scheme, Some(format)) => tasks.push(Task::perform(
+                async move {
+                    let content = format.export(&color_scheme)?;
+                    let response = SelectedFiles::save_file()
+                        .modal(true)
+                        .current_name(format.file_name(&color_scheme.name).as_str())
+                        .send()
+                        .await?
+                        .response()?;
+                    let Some(path) = response
+                        .uris()
+                        .first()
+                        .and_then(|uri| uri.to_file_path().ok())
+                    else {
+                        return Ok(None);
+                    };
+                    tokio::fs::write(&path, content).await?;
+                    Ok::<_, crate::Error>(Some(path))
+                },
+                exported,
+            )),
+            Message::ExportColorScheme(_, None) => (),
+            Message::ExportPreviewImage(color_scheme) => tasks.push(Task::perform(
+                async move {
+                    let response = SelectedFiles::save_file()
+                        .modal(true)
+                        .current_name(format!("{}.png", color_scheme.name).as_str())
+                        .filter(FileFilter::new("PNG").mimetype("image/png"))
+                        .send()
+                        .await?
+                        .response()?;
+                    let Some(path) = response
+                        .uris()
+                        .first()
+                        .and_then(|uri| uri.to_file_path().ok())
+                    else {
+                        return Ok(None);
+                    };
+                    render::save(color_scheme, path, render::DEFAULT_WIDTH)
+                        .await
+                        .map(Some)
+                },
+                exported,
+            )),
+            Message::ExportSuccess(path) => log::info!("exported the color scheme to {path}"),
+            Message::ExportCancelled => (),
+            Message::ExportError(e) => log::error!("failed to export the color scheme: {e}"),
             Message::ImportFile(f) => {
-                let Some(f) = f.uris().first() else {
-                    return Task::none();
-                };
-                if f.scheme() != "file" {
-                    return Task::none();
+                let mut sources = vec![];
+                for uri in f.uris() {
+                    match import::Source::from_uri(uri) {
+                        import::Source::File(path) if pack::is_pack(&path) => {
+                            tasks.push(self.update(Message::ImportPack(path)))
+                        }
+                        source => sources.push(source),
+                    }
+                }
+                // A color scheme picked in the file chooser is applied too, as before
+                // more than one could be picked.
+                tasks.push(self.update(Message::Import(sources, true)));
+            }
+            Message::ImportFiles(paths) => {
+                let (packs, paths): (Vec<PathBuf>, Vec<PathBuf>) =
+                    paths.into_iter().partition(|path| pack::is_pack(path));
+                for path in packs {
+                    tasks.push(self.update(Message::ImportPack(path)));
                 }
-                let Ok(path) = f.to_file_path() else {
+                let sources = paths.into_iter().map(import::Source::File).collect();
+                tasks.push(self.update(Message::Import(sources, false)));
+            }
+            Message::ImportUrl(Some(url)) => tasks.push(self.update(Message::Import(
+                vec![import::Source::Url(url.trim().to_string())],
+                false,
+            ))),
+            Message::ImportUrl(None) => (),
+            Message::PasteColorScheme => {
+                tasks.push(cosmic::iced::clipboard::read().map(Message::ImportText))
+            }
+            Message::ImportText(Some(text)) => {
+                tasks.push(self.update(Message::Import(vec![import::Source::Text(text)], false)))
+            }
+            Message::ImportText(None) => self.imports.push(import::Outcome::Failed {
+                source: fl!("clipboard"),
+                error: fl!("clipboard-empty"),
+            }),
+            Message::Import(sources, apply) => {
+                if !sources.is_empty() {
+                    tasks.push(Task::perform(import::import(sources), move |outcomes| {
+                        Message::Imported(outcomes, apply)
+                    }))
+                }
+            }
+            Message::Imported(outcomes, apply) => {
+                let installed: Vec<&ColorScheme> = outcomes
+                    .iter()
+                    .filter_map(|outcome| match outcome {
+                        import::Outcome::Installed(color_scheme) => Some(color_scheme),
+                        _ => None,
+                    })
+                    .collect();
+                match installed[..] {
+                    [] => (),
+                    [color_scheme] if apply => {
+                        tasks.push(self.update(Message::SetColorScheme(color_scheme.clone())));
+                        tasks.push(self.update(Message::ReloadColorSchemes));
+                    }
+                    _ => tasks.push(self.update(Message::ReloadColorSchemes)),
+                }
+                self.imports.extend(outcomes);
+            }
+            Message::SelectForPack(selecting) => {
+                self.pack_selection = selecting.then(BTreeSet::new);
+            }
+            Message::ToggleInPack(name) => {
+                if let Some(selection) = &mut self.pack_selection {
+                    if !selection.remove(&name) {
+                        selection.insert(name);
+                    }
+                }
+            }
+            Message::ExportPack => {
+                // Kept until the pack is written, so cancelling the save dialog keeps it.
+                let Some(selection) = &self.pack_selection else {
                     return Task::none();
                 };
-
-                let file = path.file_name().unwrap().to_str().unwrap().to_string();
-
-                let new_file = dirs::data_local_dir()
-                    .map(|dir| dir.join("themes/cosmic").join(file))
-                    .unwrap_or_default();
-
-                let color_scheme = ColorScheme {
-                    name: new_file.file_stem().unwrap().to_str().unwrap().to_string(),
-                    path: Some(new_file.clone()),
-                    link: None,
-                    author: None,
-                    theme: Default::default(),
-                };
-
-                tasks.push(self.update(Message::SetColorScheme(color_scheme.clone())));
-
-                let file_path = path.clone();
+                let color_schemes: Vec<ColorScheme> = selection
+                    .iter()
+                    .filter_map(|name| self.installed.iter().find(|c| &c.name == name))
+                    .cloned()
+                    .collect();
                 tasks.push(Task::perform(
-                    async move { (tokio::fs::read_to_string(path).await, file_path) },
-                    move |(res, path)| {
-                        if let Some(b) = res.ok().and_then(|theme| {
-                            if path.is_file() && !path.exists() {
-                                if let Err(e) = std::fs::write(path, &theme) {
-                                    log::error!(
-                                        "failed to write the file to the themes directory: {e}"
-                                    );
-                                }
-                            }
-                            ron::de::from_str(&theme).ok()
-                        }) {
-                            Message::ImportSuccess(Box::new(b))
-                        } else {
-                            log::error!("failed to import a file for a custom theme.");
-                            Message::ImportError
-                        }
+                    async move {
+                        let response = SelectedFiles::save_file()
+                            .modal(true)
+                            .current_name(format!("{}.zip", fl!("color-schemes")).as_str())
+                            .filter(FileFilter::glob(
+                                FileFilter::new(&fl!("color-scheme-packs")),
+                                "*.zip",
+                            ))
+                            .send()
+                            .await?
+                            .response()?;
+                        let Some(path) = response
+                            .uris()
+                            .first()
+                            .and_then(|uri| uri.to_file_path().ok())
+                        else {
+                            return Ok(None);
+                        };
+                        pack::export_async(color_schemes, path).await.map(Some)
                     },
-                ))
-            }
-            Message::ImportSuccess(builder) => {
-                let theme_mode_config = ThemeMode::config().ok();
-                let theme_mode = theme_mode_config
-                    .as_ref()
-                    .map(|c| match ThemeMode::get_entry(c) {
-                        Ok(t) => t,
-                        Err((errors, t)) => {
-                            for e in errors {
-                                log::error!("{e}");
-                            }
-                            t
-                        }
+                    |res| match exported(res) {
+                        Message::ExportSuccess(path) => Message::PackExported(path),
+                        message => message,
+                    },
+                ));
+            }
+            Message::PackExported(path) => {
+                self.pack_selection = None;
+                tasks.push(self.update(Message::ExportSuccess(path)));
+            }
+            Message::ImportPack(path) => {
+                let source = path.display().to_string();
+                tasks.push(Task::perform(
+                    pack::read_async(path),
+                    move |res| match res {
+                        Ok(color_schemes) => Message::PackRead(color_schemes),
+                        Err(e) => Message::Imported(
+                            vec![import::Outcome::Failed {
+                                source: source.clone(),
+                                error: e.to_string(),
+                            }],
+                            false,
+                        ),
+                    },
+                ));
+            }
+            Message::PackRead(color_schemes) => {
+                let mut outcomes = vec![];
+                let mut conflicts = vec![];
+                for color_scheme in color_schemes {
+                    match import::install(color_scheme) {
+                        import::Outcome::Collision(color_scheme) => conflicts.push(color_scheme),
+                        outcome => outcomes.push(outcome),
+                    }
+                }
+                tasks.push(self.update(Message::Imported(outcomes, false)));
+                if !conflicts.is_empty() {
+                    tasks.push(Task::done(Message::PackConflicts(conflicts)));
+                }
+            }
+            Message::PackConflicts(_) => (),
+            Message::ResolvePackConflicts(conflicts) => {
+                let outcomes = conflicts
+                    .into_iter()
+                    .filter_map(|(color_scheme, conflict)| match conflict {
+                        pack::Conflict::Skip => None,
+                        pack::Conflict::Overwrite => Some(import::replace(color_scheme)),
+                        pack::Conflict::Rename => Some(import::keep_both(color_scheme)),
                     })
-                    .unwrap_or_default();
-
-                let config = if theme_mode.is_dark {
-                    Theme::dark_config()
-                } else {
-                    Theme::light_config()
-                };
-
-                let Some(config) = config.ok() else {
-                    log::error!("Failed to get the theme config.");
+                    .collect();
+                tasks.push(self.update(Message::Imported(outcomes, false)));
+            }
+            Message::ResolveImport(index, resolution) => {
+                if index >= self.imports.len() {
+                    return Task::none();
+                }
+                let outcome = self.imports.remove(index);
+                let import::Outcome::Collision(color_scheme) = outcome else {
                     return Task::none();
                 };
-
-                if let Err(e) = builder.build().write_entry(&config) {
+                let outcome = match resolution {
+                    import::Resolution::Replace => import::replace(color_scheme),
+                    import::Resolution::KeepBoth => import::keep_both(color_scheme),
+                    import::Resolution::Dismiss => return Task::none(),
+                };
+                self.imports.insert(index, outcome);
+                tasks.push(self.update(Message::ReloadColorSchemes));
+            }
+            Message::ImportSuccess(builder, variant) => {
+                if let Err(e) =
+                    ColorScheme::set_theme(&builder, variant.as_deref(), self.keep_style)
+                {
                     log::error!("Failed to write the theme config: {e}");
                 }
                 tasks.push(self.update(Message::ReloadColorSchemes));
@@ -213,9 +549,12 @@ impl ColorSchemes {
 
                 if color_scheme.theme != ThemeBuilder::default() {
                     log::info!("Theme is not default, setting the theme...");
-                    if let Ok(theme) = &color_scheme.read_theme() {
+                    if let Ok((theme, variant)) = color_scheme.read_theme() {
                         log::info!("Color scheme has a theme, setting the theme...");
-                        tasks.push(self.update(Message::ImportSuccess(Box::new(theme.clone()))))
+                        tasks.push(self.update(Message::ImportSuccess(
+                            Box::new(theme),
+                            variant.map(Box::new),
+                        )))
                     }
                 }
             }
@@ -232,24 +571,103 @@ impl ColorSchemes {
                 std::fs::remove_file(&path).unwrap_or_else(|e| {
                     log::error!("There was an error deleting the color scheme: {e}")
                 });
+                if let Err(e) = Provenance::remove(&path) {
+                    log::error!("There was an error deleting the color scheme provenance: {e}")
+                }
+                self.labels.remove(&color_scheme.name);
+                self.save_labels();
                 tasks.push(self.update(Message::ReloadColorSchemes));
             }
-            Message::InstallColorScheme(color_scheme) => {
-                let new_file = dirs::data_local_dir()
-                    .map(|dir| {
-                        dir.join("themes/cosmic")
-                            .join(&color_scheme.name)
-                            .with_extension("ron")
-                    })
-                    .unwrap_or_default();
-
-                if let Err(e) =
-                    std::fs::write(&new_file, ron::ser::to_string(&color_scheme.theme).unwrap())
-                {
+            // Opens the properties dialog in the app.
+            Message::EditProperties(_) => (),
+            Message::SaveProperties(color_scheme, name, tags) => {
+                let saved = self.save_properties(color_scheme, &name, tags);
+                tasks.push(dialog_result(saved));
+            }
+            Message::DuplicateColorScheme(color_scheme, name, tags) => {
+                let duplicated = self.duplicate(color_scheme, &name, tags);
+                tasks.push(dialog_result(duplicated));
+            }
+            Message::GenerateOpposite(color_scheme) => {
+                match opposite::save_paired(&color_scheme) {
+                    Ok((path, failing)) => {
+                        self.opposite_contrast = (!failing.is_empty()).then(|| {
+                            let name = path.file_stem().unwrap_or_default();
+                            (name.to_string_lossy().into_owned(), failing)
+                        });
+                    }
+                    Err(e) => {
+                        log::error!("There was an error generating the opposite variant: {e}")
+                    }
+                }
+                tasks.push(self.update(Message::ReloadColorSchemes));
+            }
+            Message::DismissOppositeContrast => self.opposite_contrast = None,
+            Message::ToggleFavorite(name) => {
+                self.labels.toggle_favorite(&name);
+                self.save_labels();
+            }
+            Message::TagFilterSelected(index) => {
+                // The first option shows every color scheme.
+                self.tag_filter = match index {
+                    0 => None,
+                    index => self.tag_options.get(index).cloned(),
+                };
+            }
+            Message::FavoritesFirst(favorites_first) => self.favorites_first = favorites_first,
+            Message::VisionSelected(index) => {
+                // The first option is normal vision.
+                self.vision = index
+                    .checked_sub(1)
+                    .and_then(|index| Deficiency::all().get(index))
+                    .copied();
+                let available = self
+                    .available
+                    .values_mut()
+                    .flat_map(|page| &mut page.color_schemes)
+                    .map(|(_, built)| built);
+                for built in self.built.values_mut().chain(available) {
+                    built.simulate(self.vision);
+                }
+            }
+            Message::InstallColorScheme(mut color_scheme) => {
+                record_provenance(&mut color_scheme, self.provider.clone());
+                if let Err(e) = color_scheme.install() {
                     log::error!("There was an error installing the color scheme: {e}");
                 }
                 tasks.push(self.update(Message::ReloadColorSchemes));
             }
+            Message::CheckForUpdates => tasks.push(Task::perform(
+                provider::check_updates(self.installed.clone()),
+                Message::SetUpdates,
+            )),
+            Message::SetUpdates(updates) => self.updates = updates.into_iter().collect(),
+            Message::UpdateColorScheme(name) => {
+                let Some(provider::Update::Available(mut remote)) = self.updates.remove(&name)
+                else {
+                    return Task::none();
+                };
+                let provider = self
+                    .installed
+                    .iter()
+                    .find(|color_scheme| color_scheme.name == name)
+                    .and_then(|color_scheme| color_scheme.provenance.as_ref())
+                    .and_then(|provenance| provenance.provider.clone())
+                    .unwrap_or_default();
+                remote.name = name;
+                record_provenance(&mut remote, provider);
+                match remote.install() {
+                    Ok(path) => {
+                        // Keep the updated color scheme selected and apply its new colors.
+                        if self.color_scheme.name == remote.name {
+                            remote.path = Some(path);
+                            tasks.push(self.update(Message::SetColorScheme(remote)));
+                        }
+                    }
+                    Err(e) => log::error!("There was an error updating the color scheme: {e}"),
+                }
+                tasks.push(self.update(Message::ReloadColorSchemes));
+            }
             Message::FetchAvailableColorSchemes(provider, limit) => {
                 if self.offset == 0 {
                     self.status = Status::Loading;
@@ -257,37 +675,104 @@ impl ColorSchemes {
                     self.status = Status::LoadingMore;
                 }
                 self.limit = limit;
-                self.offset += self.limit;
-                let limit = self.limit;
+                self.provider = provider.clone();
                 let offset = self.offset;
+                self.offset += limit;
+                let query = self.query.clone();
+                let generation = self.generation;
+                // Show the cached copy right away and replace it once the provider answers.
+                let key = PageKey::new(&provider, &query, limit, offset);
+                tasks.push(
+                    Task::perform(key.read(), move |page| {
+                        page.map(|page| {
+                            Message::SetAvailableColorSchemes(
+                                generation,
+                                offset,
+                                Origin::Cache,
+                                Box::new(page),
+                            )
+                        })
+                    })
+                    .and_then(Task::done),
+                );
                 tasks.push(Task::perform(
-                    async move {
-                        let url = match provider {
-                            ColorSchemeProvider::CosmicThemes => {
-                                format!("https://cosmic-themes.org/api/themes/?order=name&limit={}&offset={}", limit, offset)
-                            }
-                        };
-
-                        let response = reqwest::get(url).await?;
-                        let themes: Vec<CosmicTheme> = response.json().await?;
-                        let available = themes
-                            .into_iter()
-                            .map(ColorScheme::from)
-                            .collect();
-                        Ok(available)
-                    },
-                    |res: Result<Vec<ColorScheme>, reqwest::Error>| match res {
-                        Ok(themes) => Message::SetAvailableColorSchemes(themes),
-                        Err(e) => {
-                            log::error!("{e}");
-                            Message::SetAvailableColorSchemes(vec![])
-                        }
+                    catalogue::fetch(provider, query, limit, offset),
+                    move |res| match res {
+                        Ok(page) => Message::SetAvailableColorSchemes(
+                            generation,
+                            offset,
+                            Origin::Network,
+                            Box::new(page),
+                        ),
+                        Err(e) => Message::FetchAvailableError(generation, offset, e.to_string()),
                     },
                 ));
             }
-            Message::SetAvailableColorSchemes(mut available) => {
+            Message::SetAvailableColorSchemes(generation, offset, origin, page) => {
+                if generation != self.generation {
+                    return Task::none();
+                }
+                // The provider answered before the cache was read.
+                if origin == Origin::Cache && self.available.contains_key(&offset) {
+                    return Task::none();
+                }
+                self.status = Status::Idle;
+                if origin == Origin::Network {
+                    self.fetch_error = None;
+                }
+                let CataloguePage {
+                    fetched_at,
+                    color_schemes,
+                } = *page;
+                // Providers filter by the query themselves, filtering again here would
+                // leave pages short and break paging.
+                let color_schemes = color_schemes
+                    .into_iter()
+                    .map(|color_scheme| {
+                        let mut built = Built::from(&color_scheme);
+                        built.simulate(self.vision);
+                        (color_scheme, built)
+                    })
+                    .collect();
+                self.available.insert(
+                    offset,
+                    AvailablePage {
+                        origin,
+                        fetched_at,
+                        color_schemes,
+                    },
+                );
+            }
+            Message::FetchAvailableError(generation, offset, e) => {
+                if generation != self.generation {
+                    return Task::none();
+                }
+                log::error!("failed to fetch the available color schemes: {e}");
                 self.status = Status::Idle;
-                self.available.append(&mut available);
+                self.fetch_error = Some(e);
+                // Let "Show more" ask for the same page again.
+                if !self.available.contains_key(&offset) {
+                    self.offset = self.offset.min(offset);
+                }
+            }
+            Message::RetryFetch => tasks.push(self.refetch()),
+            Message::Search(search) => self.query.search = search,
+            Message::SubmitSearch => tasks.push(self.refetch()),
+            Message::ClearSearch => {
+                self.query.search.clear();
+                tasks.push(self.refetch());
+            }
+            Message::SortSelected(index) => {
+                self.query.sort = SortOrder::all()[index];
+                tasks.push(self.refetch());
+            }
+            Message::ModeFilterSelected(index) => {
+                self.query.mode = ModeFilter::all()[index];
+                tasks.push(self.refetch());
+            }
+            Message::SelectProvider(provider) => {
+                self.provider = provider;
+                tasks.push(self.refetch());
             }
             Message::OpenLink(link) => {
                 if let Some(link) = link {
@@ -307,54 +792,350 @@ impl ColorSchemes {
                 }
             }
             Message::ReloadColorSchemes => {
-                self.installed = ColorScheme::installed().unwrap_or_default();
+                self.reloads += 1;
+                let reload = self.reloads;
+                tasks.push(Task::perform(self.cache.clone().load(), move |installed| {
+                    Message::SetInstalledColorSchemes(reload, Box::new(installed))
+                }));
+            }
+            Message::SetInstalledColorSchemes(reload, installed) => {
+                if reload != self.reloads {
+                    return Task::none();
+                }
+                let first_load = matches!(self.installed_status, Status::Loading);
+                self.installed_status = Status::Idle;
+                let Installed {
+                    color_schemes,
+                    built,
+                    problems,
+                } = *installed;
+                self.installed_names = color_schemes.iter().map(|c| c.name.clone()).collect();
+                self.installed = color_schemes;
+                self.built = built;
+                for built in self.built.values_mut() {
+                    built.simulate(self.vision);
+                }
+                self.problems = problems;
+                if first_load {
+                    tasks.push(self.update(Message::CheckForUpdates));
+                }
+                if let Some(name) = self.scheduled.take() {
+                    tasks.push(self.update(Message::ScheduledColorScheme(name)));
+                }
+            }
+            Message::OpenBrokenFile(path) => {
+                if let Err(e) = open::that_detached(&path) {
+                    log::error!("There was an error opening {}: {e}", path.display())
+                }
+            }
+            Message::DeleteBrokenFile(path) => {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::error!("There was an error deleting {}: {e}", path.display())
+                }
+                tasks.push(self.update(Message::ReloadColorSchemes));
+            }
+            Message::QuarantineBrokenFile(path) => {
+                match problems::quarantine(&path) {
+                    Ok(target) => log::info!("moved {} to {}", path.display(), target.display()),
+                    Err(e) => log::error!("There was an error moving {}: {e}", path.display()),
+                }
+                tasks.push(self.update(Message::ReloadColorSchemes));
+            }
+            Message::ScheduledColorScheme(name) => {
+                if self.color_scheme.name == name {
+                    return Task::none();
+                }
+                if matches!(self.installed_status, Status::Loading) {
+                    self.scheduled = Some(name);
+                    return Task::none();
+                }
+                match self.installed.iter().find(|c| c.name == name).cloned() {
+                    Some(color_scheme) => {
+                        log::info!("switching to the scheduled color scheme {name}");
+                        tasks.push(self.update(Message::SetColorScheme(color_scheme)));
+                    }
+                    None => log::error!("the scheduled color scheme {name} is not installed"),
+                }
             }
             Message::SaveCurrentColorScheme(name) => {
                 if let Some(name) = name {
-                    let path = dirs::data_local_dir()
-                        .map(|dir| dir.join("themes/cosmic").join(&name).with_extension("ron"))
-                        .unwrap_or_default();
-
-                    let color_scheme = ColorScheme {
-                        name,
-                        path: Some(path.clone()),
-                        link: None,
-                        author: None,
-                        theme: self.theme_builder.clone(),
-                    };
-
-                    if path.exists() {
-                        log::error!("The color scheme already exists.");
-                        return Task::none();
+                    let theme = self.theme_builder.clone();
+                    let saved = self.save_color_scheme(name, theme, None);
+                    tasks.push(dialog_result(saved));
+                } else {
+                    tasks.push(self.update(Message::SaveCurrentColorScheme(None)))
+                }
+            }
+            Message::EditColorScheme(color_scheme) => {
+                self.editor = Some(editor::Editor::new(color_scheme, self.vision));
+            }
+            Message::Editor(message) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.update(message);
+                }
+            }
+            Message::ApplyEditedColorScheme => {
+                if let Some(editor) = &mut self.editor {
+                    if let Err(e) = editor.apply(self.keep_style) {
+                        log::error!("Failed to write the theme config: {e}");
+                    }
+                }
+            }
+            Message::SaveEditedColorScheme(name) => {
+                let Some(name) = name else {
+                    return Task::none();
+                };
+                let Some(edited) = self.editor.as_ref().map(editor::Editor::edited) else {
+                    return Task::none();
+                };
+                let saved = self.save_color_scheme(name, edited.theme, edited.variant);
+                if saved.is_ok() {
+                    // The saved color scheme is applied now, nothing to restore.
+                    if let Some(mut editor) = self.editor.take() {
+                        editor.keep_applied();
                     }
+                }
+                tasks.push(dialog_result(saved));
+            }
+            // Dropping the editor restores the themes it applied.
+            Message::CloseEditor => self.editor = None,
+            Message::OpenCompare => {
+                let available = self
+                    .available
+                    .values()
+                    .flat_map(|page| &page.color_schemes)
+                    .map(|(color_scheme, _)| color_scheme.clone())
+                    .collect();
+                self.compare = Some(compare::Compare::new(
+                    &self.installed,
+                    available,
+                    &self.color_scheme.name,
+                    self.vision,
+                ));
+            }
+            Message::Compare(message) => {
+                if let Some(compare) = &mut self.compare {
+                    compare.update(message);
+                }
+            }
+            Message::CloseCompare => self.compare = None,
+            Message::StartGenerate => tasks.push(Task::perform(
+                async {
+                    SelectedFiles::open_file()
+                        .modal(true)
+                        .filter(
+                            FileFilter::new(&fl!("images"))
+                                .mimetype("image/png")
+                                .mimetype("image/jpeg"),
+                        )
+                        .send()
+                        .await?
+                        .response()
+                },
+                |res| match res {
+                    Ok(f) => Message::GenerateFromImage(Arc::new(f)),
+                    Err(e) => Message::GenerateError(e.to_string()),
+                },
+            )),
+            Message::GenerateFromImage(f) => {
+                let Some(path) = f.uris().first().and_then(|uri| uri.to_file_path().ok()) else {
+                    return Task::none();
+                };
+                tasks.push(Task::perform(generate::from_image(path), |res| match res {
+                    Ok((dark, light)) => Message::Generated(Box::new(dark), Box::new(light)),
+                    Err(e) => Message::GenerateError(e.to_string()),
+                }))
+            }
+            Message::GenerateFromWallpaper => {
+                tasks.push(Task::perform(generate::from_wallpaper(), |res| match res {
+                    Ok((dark, light)) => Message::Generated(Box::new(dark), Box::new(light)),
+                    Err(e) => Message::GenerateError(e.to_string()),
+                }))
+            }
+            Message::Generated(dark, light) => {
+                self.generated = Some((*dark, *light));
+                // Goes through the app, which asks for a name first.
+                tasks.push(Task::done(Message::SaveGeneratedColorScheme(None)));
+            }
+            Message::GenerateError(e) => log::error!("failed to generate a color scheme: {e}"),
+            Message::SaveGeneratedColorScheme(name) => {
+                let Some(name) = name else {
+                    return Task::none();
+                };
+                let Some((dark, light)) = self.generated.clone() else {
+                    return Task::none();
+                };
+                let (theme, variant) = match ColorScheme::theme_mode().is_dark {
+                    true => (dark, light),
+                    false => (light, dark),
+                };
+                let saved = self.save_color_scheme(name, theme, Some(variant));
+                if saved.is_ok() {
+                    self.generated = None;
+                }
+                tasks.push(dialog_result(saved));
+            }
+            Message::CloseDialog | Message::DialogError(_) | Message::Renamed(..) => (),
+        }
+        Task::batch(tasks)
+    }
 
-                    let Ok(theme_builder) = ron::to_string(&self.theme_builder) else {
-                        log::error!("failed to serialize the theme builder");
-                        return Task::none();
-                    };
+    /// Start over from the first page after the query changed.
+    fn refetch(&mut self) -> Task<Message> {
+        self.offset = 0;
+        self.generation += 1;
+        self.available.clear();
+        self.fetch_error = None;
+        self.update(Message::FetchAvailableColorSchemes(
+            self.provider.clone(),
+            self.limit,
+        ))
+    }
 
-                    if let Err(e) = std::fs::write(path, theme_builder) {
-                        log::error!("failed to write the file to the themes directory: {e}");
-                    }
+    /// Names of the installed color schemes, in the order they are shown.
+    pub fn installed_names(&self) -> &[String] {
+        &self.installed_names
+    }
 
-                    tasks.push(self.update(Message::SetColorScheme(color_scheme)));
-                    tasks.push(self.update(Message::ReloadColorSchemes))
-                } else {
-                    tasks.push(self.update(Message::SaveCurrentColorScheme(None)))
+    pub fn labels(&self) -> &Labels {
+        &self.labels
+    }
+
+    fn save_labels(&mut self) {
+        if let Err(e) = self.labels.save() {
+            log::error!("There was an error saving the color scheme labels: {e}");
+        }
+        self.tag_options = tag_options(&self.labels);
+        if self
+            .tag_filter
+            .as_ref()
+            .is_some_and(|tag| !self.tag_options.contains(tag))
+        {
+            self.tag_filter = None;
+        }
+    }
+
+    /// Close the editor when leaving the page or quitting, undoing a temporary apply.
+    pub fn close_editor(&mut self) {
+        self.editor = None;
+    }
+
+    /// Whether the editor or the compare view replaces the color scheme grids.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some() || self.compare.is_some()
+    }
+
+    /// Save a new color scheme to the themes directory and select it.
+    fn save_color_scheme(
+        &mut self,
+        name: String,
+        theme: ThemeBuilder,
+        variant: Option<ThemeBuilder>,
+    ) -> Result<Task<Message>, crate::Error> {
+        let mut color_scheme = ColorScheme {
+            name: name.trim().to_string(),
+            path: None,
+            link: None,
+            author: None,
+            theme,
+            variant,
+            provenance: None,
+        };
+        color_scheme.path = Some(color_scheme.install_new()?);
+
+        Ok(Task::batch(vec![
+            self.update(Message::SetColorScheme(color_scheme)),
+            self.update(Message::ReloadColorSchemes),
+        ]))
+    }
+
+    /// Rename an installed color scheme and set its tags, an empty name keeps the current one.
+    fn save_properties(
+        &mut self,
+        color_scheme: ColorScheme,
+        name: &str,
+        tags: Vec<String>,
+    ) -> Result<Task<Message>, crate::Error> {
+        let mut tasks = vec![];
+        let name = match name.trim() {
+            "" => color_scheme.name.clone(),
+            name => name.to_string(),
+        };
+        if name != color_scheme.name {
+            let path = color_scheme.rename(&name)?;
+            self.labels.rename(&color_scheme.name, &name);
+            if self.color_scheme.name == color_scheme.name {
+                let config = ColorScheme::config();
+                if let Err(e) = self.color_scheme.set_name(&config, name.clone()) {
+                    log::error!("There was an error selecting the color scheme: {e}");
+                }
+                if let Err(e) = self.color_scheme.set_path(&config, Some(path)) {
+                    log::error!("There was an error selecting the color scheme: {e}");
                 }
             }
+            if let Some(update) = self.updates.remove(&color_scheme.name) {
+                self.updates.insert(name.clone(), update);
+            }
+            tasks.push(Task::done(Message::Renamed(
+                color_scheme.name,
+                name.clone(),
+            )));
         }
-        Task::batch(tasks)
+        self.labels.set_tags(&name, tags);
+        self.save_labels();
+        tasks.push(self.update(Message::ReloadColorSchemes));
+        Ok(Task::batch(tasks))
+    }
+
+    /// Install a copy of a color scheme under a new name, with its own tags.
+    fn duplicate(
+        &mut self,
+        color_scheme: ColorScheme,
+        name: &str,
+        tags: Vec<String>,
+    ) -> Result<Task<Message>, crate::Error> {
+        let duplicate = ColorScheme {
+            name: name.trim().to_string(),
+            path: None,
+            link: None,
+            author: None,
+            provenance: None,
+            ..color_scheme
+        };
+        duplicate.install_new()?;
+        self.labels.set_tags(&duplicate.name, tags);
+        self.save_labels();
+        Ok(self.update(Message::ReloadColorSchemes))
     }
 
     pub fn view<'a>(&'a self) -> Element<'a, Message> {
+        if let Some(editor) = &self.editor {
+            return editor.view();
+        }
+        if let Some(compare) = &self.compare {
+            return compare.view();
+        }
+
         let spacing = cosmic::theme::spacing();
         let active_tab = self.model.active_data::<Tab>().unwrap();
+        let toolbar = match active_tab {
+            Tab::Installed => self.installed_toolbar(),
+            Tab::Available => self.available_toolbar(),
+        };
         let title = widget::text::title3(fl!("color-schemes"));
         let tabs = widget::segmented_button::horizontal(&self.model)
             .padding(spacing.space_xxxs)
             .button_alignment(cosmic::iced::Alignment::Center)
             .on_activate(Message::TabSelected);
+        let imports = (active_tab == Tab::Installed && !self.imports.is_empty())
+            .then(|| import::view(&self.imports));
+        let opposite_contrast = self
+            .opposite_contrast
+            .as_ref()
+            .filter(|_| active_tab == Tab::Installed)
+            .map(|(name, failing)| opposite::view(name, failing));
+        let problems = (active_tab == Tab::Installed && !self.problems.is_empty())
+            .then(|| problems::view(&self.problems));
         let active_tab = match active_tab {
             Tab::Installed => widget::settings::section().add(self.installed_themes()),
             Tab::Available => widget::settings::section().add(self.available_themes()),
@@ -363,13 +1144,19 @@ impl ColorSchemes {
         widget::column()
             .push(title)
             .push(tabs)
+            .push(toolbar)
+            .push_maybe(imports)
+            .push_maybe(opposite_contrast)
+            .push_maybe(problems)
             .push(active_tab)
             .spacing(spacing.space_xxs)
             .into()
     }
 
     fn installed_themes<'a>(&'a self) -> Element<'a, Message> {
-        if self.installed.is_empty() {
+        if matches!(self.installed_status, Status::Loading) {
+            widget::text(fl!("loading")).into()
+        } else if self.installed.is_empty() {
             widget::text("No color schemes installed").into()
         } else {
             widget::responsive(move |size| {
@@ -381,16 +1168,48 @@ impl ColorSchemes {
                     column_spacing,
                 } = GridMetrics::custom(&spacing, size.width as usize);
 
+                let mut installed: Vec<&ColorScheme> = self
+                    .installed
+                    .iter()
+                    .filter(|color_scheme| {
+                        self.tag_filter
+                            .as_ref()
+                            .is_none_or(|tag| self.labels.tags(&color_scheme.name).contains(tag))
+                    })
+                    .collect();
+                if self.favorites_first {
+                    installed
+                        .sort_by_key(|color_scheme| !self.labels.is_favorite(&color_scheme.name));
+                }
+
                 let mut grid = widget::grid();
                 let mut col = 0;
-                for color_scheme in self.installed.iter() {
+                for color_scheme in installed {
                     if col >= cols {
                         grid = grid.insert_row();
                         col = 0;
                     }
+                    let built = match color_scheme.path.as_ref().and_then(|p| self.built.get(p)) {
+                        Some(built) => Cow::Borrowed(built),
+                        None => {
+                            let mut built = Built::from(color_scheme);
+                            built.simulate(self.vision);
+                            Cow::Owned(built)
+                        }
+                    };
+                    let in_pack = self
+                        .pack_selection
+                        .as_ref()
+                        .map(|selection| selection.contains(&color_scheme.name));
                     grid = grid.push(preview::installed(
                         color_scheme,
-                        &self.color_scheme,
+                        &built,
+                        preview::InstalledOptions {
+                            selected: &self.color_scheme,
+                            labels: &self.labels,
+                            in_pack,
+                            update: self.updates.get(&color_scheme.name),
+                        },
                         &spacing,
                         item_width,
                     ));
@@ -408,13 +1227,105 @@ impl ColorSchemes {
         }
     }
 
+    fn installed_toolbar<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let tag = match &self.tag_filter {
+            Some(tag) => self.tag_options.iter().position(|option| option == tag),
+            None => Some(0),
+        };
+
+        widget::row()
+            .push(widget::text::body(fl!("tag")))
+            .push(widget::dropdown(
+                &self.tag_options,
+                tag,
+                Message::TagFilterSelected,
+            ))
+            .push(widget::horizontal_space())
+            .push(widget::text::body(fl!("favorites-first")))
+            .push(widget::toggler(self.favorites_first).on_toggle(Message::FavoritesFirst))
+            .push(self.vision_dropdown())
+            .push(widget::button::standard(fl!("compare")).on_press(Message::OpenCompare))
+            .push(match &self.pack_selection {
+                Some(selection) => {
+                    widget::button::suggested(fl!("export-pack", count = selection.len()))
+                        .on_press_maybe((!selection.is_empty()).then_some(Message::ExportPack))
+                }
+                None => widget::button::standard(fl!("select-for-pack"))
+                    .on_press(Message::SelectForPack(true)),
+            })
+            .push_maybe(self.pack_selection.is_some().then(|| {
+                widget::button::standard(fl!("cancel")).on_press(Message::SelectForPack(false))
+            }))
+            .align_y(cosmic::iced::Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .into()
+    }
+
+    /// Simulate a color vision deficiency in every preview card.
+    fn vision_dropdown<'a>(&'a self) -> Element<'a, Message> {
+        let selected = match self.vision {
+            Some(deficiency) => Deficiency::all()
+                .iter()
+                .position(|d| *d == deficiency)
+                .map(|index| index + 1),
+            None => Some(0),
+        };
+        widget::row()
+            .push(widget::text::body(fl!("vision")))
+            .push(widget::dropdown(
+                &self.vision_options,
+                selected,
+                Message::VisionSelected,
+            ))
+            .align_y(cosmic::iced::Alignment::Center)
+            .spacing(cosmic::theme::spacing().space_xxs)
+            .into()
+    }
+
+    fn available_toolbar<'a>(&'a self) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let sort = SortOrder::all()
+            .iter()
+            .position(|sort| *sort == self.query.sort);
+        let mode = ModeFilter::all()
+            .iter()
+            .position(|mode| *mode == self.query.mode);
+
+        widget::row()
+            .push(
+                widget::search_input(fl!("search-color-schemes"), &self.query.search)
+                    .on_input(Message::Search)
+                    .on_submit(|_| Message::SubmitSearch)
+                    .on_clear(Message::ClearSearch),
+            )
+            .push(widget::dropdown(
+                &self.sort_options,
+                sort,
+                Message::SortSelected,
+            ))
+            .push(widget::dropdown(
+                &self.mode_options,
+                mode,
+                Message::ModeFilterSelected,
+            ))
+            .push(self.vision_dropdown())
+            .push(widget::button::standard(fl!("compare")).on_press(Message::OpenCompare))
+            .align_y(cosmic::iced::Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .into()
+    }
+
     fn available_themes<'a>(&'a self) -> Element<'a, Message> {
         match self.status {
             Status::Idle | Status::LoadingMore => {
                 if self.available.is_empty() {
-                    widget::text("No color schemes found").into()
+                    match &self.fetch_error {
+                        Some(error) => self.fetch_error_view(error),
+                        None => widget::text("No color schemes found").into(),
+                    }
                 } else {
-                    widget::responsive(move |size| {
+                    let grid = widget::responsive(move |size| {
                         let spacing = cosmic::theme::spacing();
 
                         let GridMetrics {
@@ -425,14 +1336,19 @@ impl ColorSchemes {
 
                         let mut grid = widget::grid();
                         let mut col = 0;
-                        for color_scheme in self.available.iter() {
+                        let available =
+                            self.available.values().flat_map(|page| &page.color_schemes);
+                        for (color_scheme, built) in available {
                             if col >= cols {
                                 grid = grid.insert_row();
                                 col = 0;
                             }
-
-                            grid =
-                                grid.push(preview::available(color_scheme, &spacing, item_width));
+                            grid = grid.push(preview::available(
+                                color_scheme,
+                                built,
+                                &spacing,
+                                item_width,
+                            ));
                             col += 1;
                         }
 
@@ -442,11 +1358,103 @@ impl ColorSchemes {
                         )
                         .spacing(spacing.space_xxs)
                         .into()
-                    })
-                    .into()
+                    });
+
+                    widget::column()
+                        .push_maybe(
+                            self.fetch_error
+                                .as_ref()
+                                .map(|error| self.offline_banner(error)),
+                        )
+                        .push(grid)
+                        .spacing(cosmic::theme::spacing().space_xxs)
+                        .into()
                 }
             }
             Status::Loading => widget::text(fl!("loading")).into(),
         }
     }
+
+    /// Shown instead of the grid when a fetch failed and nothing is cached.
+    fn fetch_error_view<'a>(&'a self, error: &'a str) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        widget::column()
+            .push(widget::text(fl!("catalogue-error")))
+            .push(widget::text::caption(error))
+            .push(widget::button::standard(fl!("retry")).on_press(Message::RetryFetch))
+            .align_x(cosmic::iced::Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .into()
+    }
+
+    /// Shown above the grid when a fetch failed, saying how old the cached pages are.
+    fn offline_banner<'a>(&'a self, error: &'a str) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let cached_at = self
+            .available
+            .values()
+            .filter(|page| page.origin == Origin::Cache)
+            .map(|page| page.fetched_at)
+            .min();
+        let message = match cached_at {
+            Some(cached_at) => fl!(
+                "showing-cached-color-schemes",
+                date = cached_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            ),
+            None => fl!("catalogue-error"),
+        };
+
+        widget::row()
+            .push(
+                widget::column()
+                    .push(widget::text(message))
+                    .push(widget::text::caption(error))
+                    .width(cosmic::iced::Length::Fill),
+            )
+            .push(widget::button::standard(fl!("retry")).on_press(Message::RetryFetch))
+            .align_y(cosmic::iced::Alignment::Center)
+            .spacing(spacing.space_xxs)
+            .apply(widget::container)
+            .class(cosmic::style::Container::Card)
+            .padding(spacing.space_xxs)
+            .into()
+    }
+}
+
+/// Close the dialog that started an action, or keep it open with the error.
+fn dialog_result(result: Result<Task<Message>, crate::Error>) -> Task<Message> {
+    match result {
+        Ok(task) => Task::batch(vec![task, Task::done(Message::CloseDialog)]),
+        Err(e) => Task::done(Message::DialogError(e.to_string())),
+    }
+}
+
+/// Report where an export was saved, ignoring a cancelled save dialog.
+fn exported(result: Result<Option<PathBuf>, crate::Error>) -> Message {
+    match result {
+        Ok(Some(path)) => Message::ExportSuccess(path.display().to_string()),
+        Ok(None) => Message::ExportCancelled,
+        Err(e) => Message::ExportError(e.to_string()),
+    }
+}
+
+/// The options of the tag filter, "All" first.
+fn tag_options(labels: &Labels) -> Vec<String> {
+    std::iter::once(fl!("all"))
+        .chain(labels.all_tags())
+        .collect()
+}
+
+/// Remember where a color scheme is installed from, for the installed cards and update checks.
+fn record_provenance(color_scheme: &mut ColorScheme, provider: ColorSchemeProvider) {
+    let mut provenance = color_scheme.provenance.take().unwrap_or_default();
+    provenance.author = provenance.author.or_else(|| color_scheme.author.clone());
+    provenance.link = provenance.link.or_else(|| color_scheme.link.clone());
+    provenance.provider = Some(provider);
+    provenance.installed_at = Some(Utc::now());
+    provenance.content_hash = provenance::content_hash(color_scheme).ok();
+    color_scheme.provenance = Some(provenance);
 }
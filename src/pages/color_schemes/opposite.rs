@@ -0,0 +1,257 @@
+//! Derives the opposite mode of a color scheme by inverting lightness in OKLCH.
+
+use std::path::{Path, PathBuf};
+
+use cosmic::{
+    cosmic_theme::{
+        palette::{Srgb, Srgba},
+        CosmicPalette, CosmicPaletteInner, ThemeBuilder,
+    },
+    iced::{Alignment, Length},
+    widget::{self, tooltip},
+    Apply, Element,
+};
+
+use crate::{
+    core::{
+        color::{contrast_ratio, lightness, with_lightness},
+        icons,
+    },
+    fl, Error,
+};
+
+use super::{
+    config::ColorScheme,
+    contrast::{self, ContrastCheck, AA_TEXT},
+};
+
+/// Step of OKLCH lightness the accent is moved by until it is readable.
+const ACCENT_STEP: f32 = 0.02;
+
+/// Appended to the name of a color scheme for the file of its generated pair.
+/// Not localized, so the file name doesn't depend on the language it was made in.
+const PAIRED_SUFFIX: &str = " (Dark and Light)";
+
+/// A generated opposite mode and the contrast checks it still fails.
+pub struct Opposite {
+    pub theme: ThemeBuilder,
+    pub failing: Vec<ContrastCheck>,
+}
+
+/// Generate the light mode of a dark theme, or the dark mode of a light one.
+///
+/// The background lightness is mirrored between the default backgrounds of both
+/// modes, and containers keep their offset from the background, so raised surfaces
+/// stay raised. Every other color, in the palette and the overrides, has its
+/// lightness flipped. Hues are kept. The accent, success, warning and destructive
+/// colors then only move in lightness until they reach AA contrast on the new
+/// background.
+pub fn opposite(theme: &ThemeBuilder) -> Opposite {
+    let (default, default_opposite) = if theme.palette.is_dark() {
+        (ThemeBuilder::dark(), ThemeBuilder::light())
+    } else {
+        (ThemeBuilder::light(), ThemeBuilder::dark())
+    };
+    let built = theme.clone().build();
+    let anchor = lightness(default.build().background.base);
+    let opposite_anchor = lightness(default_opposite.build().background.base);
+    let background = lightness(built.background.base);
+    let opposite_background = opposite_anchor - (background - anchor);
+    let invert =
+        |color: Srgba| with_lightness(color, opposite_background + (lightness(color) - background));
+    let flip_opaque = |color: Srgb| flip(Srgba::from(color)).color;
+
+    let mut opposite = theme.clone();
+    opposite.palette = match theme.palette.clone() {
+        CosmicPalette::Dark(palette) => CosmicPalette::Light(flip_palette(palette)),
+        CosmicPalette::Light(palette) => CosmicPalette::Dark(flip_palette(palette)),
+        CosmicPalette::HighContrastDark(palette) => {
+            CosmicPalette::HighContrastLight(flip_palette(palette))
+        }
+        CosmicPalette::HighContrastLight(palette) => {
+            CosmicPalette::HighContrastDark(flip_palette(palette))
+        }
+    };
+    opposite.bg_color = Some(invert(built.background.base));
+    opposite.primary_container_bg = theme.primary_container_bg.map(invert);
+    opposite.secondary_container_bg = theme.secondary_container_bg.map(invert);
+    opposite.text_tint = theme.text_tint.map(flip_opaque);
+    opposite.neutral_tint = theme.neutral_tint.map(flip_opaque);
+    opposite.window_hint = theme.window_hint.map(flip_opaque);
+
+    let new_background = opposite.clone().build().background.base;
+    let readable_opaque = |color: Srgb| readable(flip_opaque(color).into(), new_background).color;
+    opposite.accent = Some(readable_opaque(built.accent.base.color));
+    opposite.success = theme.success.map(readable_opaque);
+    opposite.warning = theme.warning.map(readable_opaque);
+    opposite.destructive = theme.destructive.map(readable_opaque);
+
+    let failing = contrast::checks(&opposite.clone().build())
+        .into_iter()
+        .filter(|check| !check.passes())
+        .collect();
+    Opposite {
+        theme: opposite,
+        failing,
+    }
+}
+
+/// The color with its lightness flipped, so light colors become dark ones.
+fn flip(color: Srgba) -> Srgba {
+    with_lightness(color, 1.0 - lightness(color))
+}
+
+/// Flip every color of a palette, so its neutrals run the other way.
+fn flip_palette(mut palette: CosmicPaletteInner) -> CosmicPaletteInner {
+    for color in [
+        &mut palette.bright_red,
+        &mut palette.bright_green,
+        &mut palette.bright_orange,
+        &mut palette.gray_1,
+        &mut palette.gray_2,
+        &mut palette.neutral_0,
+        &mut palette.neutral_1,
+        &mut palette.neutral_2,
+        &mut palette.neutral_3,
+        &mut palette.neutral_4,
+        &mut palette.neutral_5,
+        &mut palette.neutral_6,
+        &mut palette.neutral_7,
+        &mut palette.neutral_8,
+        &mut palette.neutral_9,
+        &mut palette.neutral_10,
+        &mut palette.accent_blue,
+        &mut palette.accent_indigo,
+        &mut palette.accent_purple,
+        &mut palette.accent_pink,
+        &mut palette.accent_red,
+        &mut palette.accent_orange,
+        &mut palette.accent_yellow,
+        &mut palette.accent_green,
+        &mut palette.accent_warm_grey,
+        &mut palette.ext_warm_grey,
+        &mut palette.ext_orange,
+        &mut palette.ext_yellow,
+        &mut palette.ext_blue,
+        &mut palette.ext_purple,
+        &mut palette.ext_pink,
+        &mut palette.ext_indigo,
+    ] {
+        *color = flip(*color);
+    }
+    palette
+}
+
+/// Move `color` away from the lightness of `background` until it is readable on it.
+fn readable(color: Srgba, background: Srgba) -> Srgba {
+    let step = if lightness(background) > 0.5 {
+        -ACCENT_STEP
+    } else {
+        ACCENT_STEP
+    };
+    let mut readable = color;
+    while contrast_ratio(readable, background) < AA_TEXT {
+        let next = lightness(readable) + step;
+        if !(0.0..=1.0).contains(&next) {
+            break;
+        }
+        readable = with_lightness(readable, next);
+    }
+    readable
+}
+
+/// Generate the opposite mode of a single mode color scheme and save both as a
+/// pair next to the original file, or in the user's themes directory when the
+/// original is in a read only system directory.
+pub fn save_paired(color_scheme: &ColorScheme) -> Result<(PathBuf, Vec<ContrastCheck>), Error> {
+    let Opposite { theme, failing } = opposite(&color_scheme.theme);
+    let paired = ColorScheme {
+        name: format!("{}{PAIRED_SUFFIX}", color_scheme.name),
+        path: None,
+        link: color_scheme.link.clone(),
+        author: color_scheme.author.clone(),
+        theme: color_scheme.theme.clone(),
+        variant: Some(theme),
+        provenance: None,
+    };
+    let ron = paired.to_ron()?;
+
+    let dirs = color_scheme
+        .path
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(ColorScheme::themes_dir());
+    for dir in dirs {
+        let path = dir.join(format!("{}.ron", paired.name));
+        if path.exists() {
+            return Err(Error::AlreadyExists("color scheme", paired.name));
+        }
+        match std::fs::write(&path, &ron) {
+            Ok(()) => return Ok((path, failing)),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(Error::ThemePathNotFound)
+}
+
+/// The contrast checks a generated pair fails, shown above the installed color
+/// schemes until dismissed.
+pub fn view<'a>(name: &str, failing: &'a [ContrastCheck]) -> Element<'a, super::Message> {
+    let spacing = cosmic::theme::spacing();
+    let checks = failing.iter().fold(
+        widget::column().push(widget::text(fl!("opposite-contrast-fails", name = name))),
+        |column, check| {
+            column.push(widget::text::caption(format!(
+                "{} — {:.2}:1 / {}:1",
+                check.pair, check.ratio, check.minimum
+            )))
+        },
+    );
+    widget::settings::section()
+        .add(
+            widget::row()
+                .push(checks.spacing(spacing.space_xxxs).width(Length::Fill))
+                .push(widget::tooltip::tooltip(
+                    icons::get_handle("window-close-symbolic", 14)
+                        .apply(widget::button::icon)
+                        .padding(spacing.space_xxs)
+                        .on_press(super::Message::DismissOppositeContrast),
+                    widget::text(fl!("dismiss")),
+                    tooltip::Position::Bottom,
+                ))
+                .align_y(Alignment::Center)
+                .spacing(spacing.space_xxs),
+        )
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readable_moves_away_from_the_background() {
+        let dark = Srgba::new(0.1, 0.1, 0.12, 1.0);
+        let light = Srgba::new(0.95, 0.95, 0.95, 1.0);
+        let blue = Srgba::new(0.1, 0.2, 0.6, 1.0);
+
+        let on_dark = readable(blue, dark);
+        assert!(contrast_ratio(on_dark, dark) >= AA_TEXT);
+        assert!(lightness(on_dark) > lightness(blue));
+
+        let pale_blue = Srgba::new(0.6, 0.7, 0.95, 1.0);
+        let on_light = readable(pale_blue, light);
+        assert!(contrast_ratio(on_light, light) >= AA_TEXT);
+        assert!(lightness(on_light) < lightness(pale_blue));
+    }
+
+    #[test]
+    fn readable_keeps_colors_that_already_pass() {
+        let black = Srgba::new(0.0, 0.0, 0.0, 1.0);
+        let white = Srgba::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(readable(white, black), white);
+    }
+}
@@ -0,0 +1,140 @@
+//! Packs of color schemes, zip archives with a manifest, to share a set of
+//! color schemes in one file.
+
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{fl, Error};
+
+use super::{config::ColorScheme, provenance::Provenance};
+
+const MANIFEST: &str = "manifest.ron";
+
+/// Lists the color schemes of a pack, stored as `manifest.ron` at its root.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub color_schemes: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    /// The color scheme file in the archive.
+    pub file: String,
+    pub author: Option<String>,
+    pub link: Option<String>,
+}
+
+/// What to do with a color scheme in a pack whose name is already installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Conflict {
+    #[default]
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl Conflict {
+    pub fn all() -> &'static [Self] {
+        &[Self::Skip, Self::Overwrite, Self::Rename]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Skip => fl!("skip"),
+            Self::Overwrite => fl!("overwrite"),
+            Self::Rename => fl!("rename"),
+        }
+    }
+}
+
+/// Whether a file is a pack rather than a single color scheme.
+pub fn is_pack(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// Write the color schemes and their manifest to a zip archive at `path`.
+pub fn export(color_schemes: &[ColorScheme], path: &Path) -> Result<(), Error> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut manifest = Manifest::default();
+
+    for color_scheme in color_schemes {
+        let file = format!("{}.ron", color_scheme.name);
+        zip.start_file(file.as_str(), options)?;
+        zip.write_all(color_scheme.to_ron()?.as_bytes())?;
+        manifest.color_schemes.push(ManifestEntry {
+            name: color_scheme.name.clone(),
+            file,
+            author: color_scheme.author.clone(),
+            link: color_scheme.link.clone(),
+        });
+    }
+
+    zip.start_file(MANIFEST, options)?;
+    let manifest = ron::ser::to_string_pretty(&manifest, ron::ser::PrettyConfig::default())?;
+    zip.write_all(manifest.as_bytes())?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// Read every color scheme listed in the manifest of a pack, without installing them.
+pub fn read(path: &Path) -> Result<Vec<ColorScheme>, Error> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let manifest: Manifest = ron::from_str(&read_entry(&mut archive, MANIFEST)?)?;
+
+    manifest
+        .color_schemes
+        .into_iter()
+        .map(|entry| {
+            ColorScheme::validate_name(&entry.name)?;
+            let (theme, variant) = ColorScheme::parse(&read_entry(&mut archive, &entry.file)?)?;
+            let provenance = (entry.author.is_some() || entry.link.is_some()).then(|| Provenance {
+                author: entry.author.clone(),
+                link: entry.link.clone(),
+                installed_at: Some(Utc::now()),
+                ..Default::default()
+            });
+            Ok(ColorScheme {
+                name: entry.name,
+                path: None,
+                link: entry.link,
+                author: entry.author,
+                theme,
+                variant,
+                provenance,
+            })
+        })
+        .collect()
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String, Error> {
+    let mut content = String::new();
+    archive.by_name(name)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Read a pack on a blocking thread.
+pub async fn read_async(path: PathBuf) -> Result<Vec<ColorScheme>, Error> {
+    tokio::task::spawn_blocking(move || read(&path))
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+}
+
+/// Write a pack on a blocking thread, returning its path.
+pub async fn export_async(
+    color_schemes: Vec<ColorScheme>,
+    path: PathBuf,
+) -> Result<PathBuf, Error> {
+    tokio::task::spawn_blocking(move || export(&color_schemes, &path).map(|()| path))
+        .await
+        .map_err(|e| Error::Io(std::io::Error::other(e)))?
+}
@@ -1,44 +1,132 @@
 use crate::{
     core::{
         icons,
-        style::{destructive_button, link_button, standard_button},
+        style::{self, destructive_button, link_button, standard_button},
     },
     fl,
 };
 use cosmic::{
-    iced::{Alignment, Length},
+    cosmic_theme::Theme,
+    iced::{Alignment, Color, Length},
     widget::{self, tooltip},
     Apply, Element,
 };
 
-use super::config::ColorScheme;
+use super::{cache::Built, config::ColorScheme, contrast, labels::Labels, provider::Update};
+
+/// The state of the page an installed color scheme card shows.
+pub struct InstalledOptions<'b> {
+    /// The color scheme selected in the config.
+    pub selected: &'b ColorScheme,
+    pub labels: &'b Labels,
+    /// Whether the color scheme is in the pack, while selecting one.
+    pub in_pack: Option<bool>,
+    pub update: Option<&'b Update>,
+}
 
 pub fn installed<'a>(
     color_scheme: &ColorScheme,
-    selected: &ColorScheme,
+    built: &Built,
+    options: InstalledOptions<'_>,
     spacing: &cosmic::cosmic_theme::Spacing,
     item_width: usize,
 ) -> Element<'a, super::Message> {
-    let theme = color_scheme.theme.clone().build();
+    let InstalledOptions {
+        selected,
+        labels,
+        in_pack,
+        update,
+    } = options;
+    let Built {
+        theme,
+        variant,
+        contrast: checks,
+        ..
+    } = built.seen();
     let color_scheme_name = color_scheme.name.clone();
+    let tags = labels.tags(&color_scheme.name);
+    let favorite = labels.is_favorite(&color_scheme.name);
+    let update_available = matches!(update, Some(Update::Available(_)));
     widget::column()
         .push(
             widget::row()
-                .push(widget::horizontal_space())
-                .push(widget::text(color_scheme_name))
-                .push(widget::horizontal_space())
+                .push_maybe(in_pack.map(|in_pack| {
+                    let name = color_scheme.name.clone();
+                    widget::checkbox("", in_pack)
+                        .on_toggle(move |_| super::Message::ToggleInPack(name.clone()))
+                }))
+                .push(
+                    widget::column()
+                        .push(widget::text(color_scheme_name))
+                        .push_maybe(color_scheme.author.clone().map(widget::text::caption))
+                        .push_maybe(
+                            (!tags.is_empty()).then(|| widget::text::caption(tags.join(" · "))),
+                        )
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
+                )
+                .push(widget::tooltip::tooltip(
+                    icons::get_handle(
+                        if favorite {
+                            "starred-symbolic"
+                        } else {
+                            "non-starred-symbolic"
+                        },
+                        14,
+                    )
+                    .apply(widget::button::icon)
+                    .class(link_button(theme.clone()))
+                    .padding(spacing.space_xxs)
+                    .on_press(super::Message::ToggleFavorite(color_scheme.name.clone())),
+                    widget::text(if favorite {
+                        fl!("remove-from-favorites")
+                    } else {
+                        fl!("add-to-favorites")
+                    }),
+                    tooltip::Position::Bottom,
+                ))
+                .push_maybe(update_available.then(|| {
+                    widget::text::caption(fl!("update"))
+                        .apply(widget::container)
+                        .padding([0, spacing.space_xxs])
+                        .class(crate::core::style::badge(
+                            theme.accent.base.into(),
+                            theme.accent.on.into(),
+                        ))
+                }))
+                .push_maybe(matches!(update, Some(Update::CannotCheck)).then(|| {
+                    widget::tooltip::tooltip(
+                        widget::text::caption(fl!("update-unknown"))
+                            .apply(widget::container)
+                            .padding([0, spacing.space_xxs])
+                            .class(crate::core::style::badge(
+                                theme.background.component.base.into(),
+                                theme.background.component.on.into(),
+                            )),
+                        widget::text(fl!("update-unknown-description")),
+                        tooltip::Position::Bottom,
+                    )
+                }))
+                .push(contrast::badge(theme, checks, spacing))
+                .align_y(Alignment::Center)
+                .spacing(spacing.space_xxxs)
                 .padding(spacing.space_xxs),
         )
+        .push(mock_row(theme, variant.as_ref(), spacing))
         .push(
             widget::row()
-                .push(
-                    widget::container(widget::text(fl!("navigation")))
-                        .padding(spacing.space_xxs)
-                        .width(90.0)
-                        .height(Length::Fill)
-                        .class(crate::core::style::card(theme.clone())),
-                )
                 .push(widget::horizontal_space())
+                .push_maybe(update_available.then(|| {
+                    widget::tooltip::tooltip(
+                        icons::get_handle("arrow-circular-bottom-right-symbolic", 14)
+                            .apply(widget::button::icon)
+                            .class(standard_button(theme.clone()))
+                            .padding(spacing.space_xxs)
+                            .on_press(super::Message::UpdateColorScheme(color_scheme.name.clone())),
+                        widget::text(fl!("update-available")),
+                        tooltip::Position::Bottom,
+                    )
+                }))
                 .push(widget::tooltip::tooltip(
                     icons::get_handle("selection-mode-symbolic", 14)
                         .apply(widget::button::icon)
@@ -53,6 +141,62 @@ pub fn installed<'a>(
                     widget::text(fl!("set-color-scheme")),
                     tooltip::Position::Bottom,
                 ))
+                .push(widget::tooltip::tooltip(
+                    icons::get_handle("edit-symbolic", 14)
+                        .apply(widget::button::icon)
+                        .class(link_button(theme.clone()))
+                        .padding(spacing.space_xxs)
+                        .on_press(super::Message::EditColorScheme(color_scheme.clone())),
+                    widget::text(fl!("edit-color-scheme")),
+                    tooltip::Position::Bottom,
+                ))
+                .push(widget::tooltip::tooltip(
+                    icons::get_handle("document-properties-symbolic", 14)
+                        .apply(widget::button::icon)
+                        .class(link_button(theme.clone()))
+                        .padding(spacing.space_xxs)
+                        .on_press(super::Message::EditProperties(color_scheme.clone())),
+                    widget::text(fl!("color-scheme-properties")),
+                    tooltip::Position::Bottom,
+                ))
+                .push_maybe(variant.is_none().then(|| {
+                    widget::tooltip::tooltip(
+                        icons::get_handle("view-dual-symbolic", 14)
+                            .apply(widget::button::icon)
+                            .class(link_button(theme.clone()))
+                            .padding(spacing.space_xxs)
+                            .on_press(super::Message::GenerateOpposite(color_scheme.clone())),
+                        widget::text(if theme.is_dark {
+                            fl!("generate-light-variant")
+                        } else {
+                            fl!("generate-dark-variant")
+                        }),
+                        tooltip::Position::Bottom,
+                    )
+                }))
+                .push(widget::tooltip::tooltip(
+                    icons::get_handle("document-save-symbolic", 14)
+                        .apply(widget::button::icon)
+                        .class(link_button(theme.clone()))
+                        .padding(spacing.space_xxs)
+                        .on_press(super::Message::ExportColorScheme(
+                            color_scheme.clone(),
+                            None,
+                        )),
+                    widget::text(fl!("export-as")),
+                    tooltip::Position::Bottom,
+                ))
+                .push_maybe(color_scheme.link.as_ref().map(|link| {
+                    widget::tooltip::tooltip(
+                        icons::get_handle("search-global-symbolic", 14)
+                            .apply(widget::button::icon)
+                            .class(link_button(theme.clone()))
+                            .padding(spacing.space_xxs)
+                            .on_press(super::Message::OpenLink(Some(link.clone()))),
+                        widget::text(fl!("open-source-page")),
+                        tooltip::Position::Bottom,
+                    )
+                }))
                 .push(widget::tooltip::tooltip(
                     icons::get_handle("symbolic-link-symbolic", 14)
                         .apply(widget::button::icon)
@@ -76,7 +220,7 @@ pub fn installed<'a>(
                 .padding([0, spacing.space_xxs, spacing.space_xxs, spacing.space_xxs]),
         )
         .width(item_width as f32)
-        .height(160.)
+        .height(220.)
         .apply(widget::container)
         .class(crate::core::style::background(&theme))
         .into()
@@ -84,33 +228,38 @@ pub fn installed<'a>(
 
 pub fn available<'a>(
     color_scheme: &'a ColorScheme,
+    built: &Built,
     spacing: &cosmic::cosmic_theme::Spacing,
     item_width: usize,
 ) -> Element<'a, super::Message> {
-    let theme = color_scheme.theme.clone().build();
+    let Built {
+        theme,
+        variant,
+        contrast: checks,
+        ..
+    } = built.seen();
     widget::column()
         .push(
-            widget::column()
-                .push(widget::text(&color_scheme.name))
-                .push_maybe(
-                    color_scheme
-                        .author
-                        .as_ref()
-                        .and_then(|author| Some(widget::text::caption(author.clone()))),
+            widget::row()
+                .push(
+                    widget::column()
+                        .push(widget::text(&color_scheme.name))
+                        .push_maybe(
+                            color_scheme
+                                .author
+                                .as_ref()
+                                .and_then(|author| Some(widget::text::caption(author.clone()))),
+                        )
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
                 )
-                .width(Length::Fill)
-                .align_x(Alignment::Center)
-                .padding([spacing.space_xxs, spacing.space_none]),
+                .push(contrast::badge(theme, checks, spacing))
+                .align_y(Alignment::Center)
+                .padding([spacing.space_xxs, spacing.space_xxs]),
         )
+        .push(mock_row(theme, variant.as_ref(), spacing))
         .push(
             widget::row()
-                .push(
-                    widget::container(widget::text(fl!("navigation")))
-                        .padding(spacing.space_xxs)
-                        .width(90.0)
-                        .height(Length::Fill)
-                        .class(crate::core::style::card(theme.clone())),
-                )
                 .push(widget::horizontal_space())
                 .push(widget::tooltip::tooltip(
                     icons::get_handle("symbolic-link-symbolic", 14)
@@ -135,8 +284,215 @@ pub fn available<'a>(
                 .padding([0, spacing.space_xxs, spacing.space_xxs, spacing.space_xxs]),
         )
         .width(item_width as f32)
-        .height(160.)
+        .height(220.)
         .apply(widget::container)
         .class(crate::core::style::background(&theme))
         .into()
 }
+
+/// A preview card without any actions.
+pub fn preview<'a>(
+    name: &str,
+    built: &Built,
+    spacing: &cosmic::cosmic_theme::Spacing,
+    item_width: usize,
+) -> Element<'a, super::Message> {
+    let Built { theme, variant, .. } = built.seen();
+    widget::column()
+        .push(
+            widget::row()
+                .push(widget::horizontal_space())
+                .push(widget::text(name.to_string()))
+                .push(widget::horizontal_space())
+                .padding(spacing.space_xxs),
+        )
+        .push(mock_row(theme, variant.as_ref(), spacing))
+        .width(item_width as f32)
+        .height(180.)
+        .apply(widget::container)
+        .class(crate::core::style::background(&theme))
+        .into()
+}
+
+/// The previewed theme and its opposite variant next to each other.
+fn mock_row<'a>(
+    theme: &Theme,
+    variant: Option<&Theme>,
+    spacing: &cosmic::cosmic_theme::Spacing,
+) -> Element<'a, super::Message> {
+    widget::row()
+        .push(mock_window(theme, spacing))
+        .push_maybe(variant.map(|variant| mock_window(variant, spacing)))
+        .height(Length::Fill)
+        .spacing(spacing.space_xxs)
+        .padding([0, spacing.space_xxs, spacing.space_xxs, spacing.space_xxs])
+        .into()
+}
+
+/// A miniature window drawn with the colors and corner radii of `theme`: a header
+/// bar, a navigation sidebar with a selected row, text at each level, buttons, a
+/// text input and toggles.
+fn mock_window<'a>(
+    theme: &Theme,
+    spacing: &cosmic::cosmic_theme::Spacing,
+) -> Element<'a, super::Message> {
+    let radii = &theme.corner_radii;
+
+    let header = widget::row()
+        .push(label(
+            fl!("mock-window-title"),
+            9,
+            theme.background.on.into(),
+        ))
+        .push(widget::horizontal_space())
+        .push(dot(theme.background.component.base.into()))
+        .push(dot(theme.background.component.base.into()))
+        .push(dot(theme.accent.base.into()))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxxs)
+        .padding([spacing.space_xxxs, spacing.space_xxs])
+        .apply(widget::container)
+        .width(Length::Fill)
+        .class(style::surface(
+            theme.background.component.base.into(),
+            [radii.radius_s[0], radii.radius_s[1], 0.0, 0.0],
+        ));
+
+    let mut selected = Color::from(theme.accent.base);
+    selected.a = 0.2;
+    let sidebar = widget::column()
+        .push(
+            label(fl!("navigation"), 8, theme.accent.base.into())
+                .apply(widget::container)
+                .width(Length::Fill)
+                .padding([1, spacing.space_xxxs])
+                .class(style::surface(selected, radii.radius_s)),
+        )
+        .push(
+            label(fl!("mock-sidebar-item"), 8, theme.primary.on.into())
+                .apply(widget::container)
+                .padding([1, spacing.space_xxxs]),
+        )
+        .spacing(spacing.space_xxxs)
+        .padding(spacing.space_xxxs)
+        .width(Length::FillPortion(2))
+        .height(Length::Fill)
+        .apply(widget::container)
+        .class(style::surface(theme.primary.base.into(), radii.radius_s));
+
+    let mut secondary_text = Color::from(theme.background.on);
+    secondary_text.a = 0.7;
+    let buttons = widget::row()
+        .push(pill(
+            fl!("save"),
+            theme.accent_button.base.into(),
+            theme.accent_button.on.into(),
+            radii.radius_xl,
+        ))
+        .push(pill(
+            fl!("cancel"),
+            theme.button.base.into(),
+            theme.button.on.into(),
+            radii.radius_xl,
+        ))
+        .push(pill(
+            fl!("mock-delete"),
+            theme.destructive_button.base.into(),
+            theme.destructive_button.on.into(),
+            radii.radius_xl,
+        ))
+        .spacing(spacing.space_xxxs);
+
+    let mut placeholder = Color::from(theme.background.component.on);
+    placeholder.a = 0.6;
+    let controls = widget::row()
+        .push(
+            label(fl!("mock-text-input"), 8, placeholder)
+                .apply(widget::container)
+                .padding([1, spacing.space_xxxs])
+                .width(Length::Fill)
+                .class(style::surface_outlined(
+                    theme.background.component.base.into(),
+                    theme.accent.base.into(),
+                    radii.radius_s,
+                )),
+        )
+        .push(toggle(theme, true))
+        .push(toggle(theme, false))
+        .align_y(Alignment::Center)
+        .spacing(spacing.space_xxxs);
+
+    let content = widget::column()
+        .push(label(fl!("mock-heading"), 10, theme.background.on.into()))
+        .push(label(fl!("mock-body"), 8, secondary_text))
+        .push(label(fl!("mock-link"), 8, theme.accent.base.into()))
+        .push(buttons)
+        .push(controls)
+        .spacing(spacing.space_xxxs)
+        .width(Length::FillPortion(5));
+
+    let body = widget::row()
+        .push(sidebar)
+        .push(content)
+        .spacing(spacing.space_xxs)
+        .padding(spacing.space_xxs)
+        .height(Length::Fill);
+
+    widget::column()
+        .push(header)
+        .push(body)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .apply(widget::container)
+        .class(style::surface_outlined(
+            theme.background.base.into(),
+            theme.background.divider.into(),
+            radii.radius_s,
+        ))
+        .into()
+}
+
+fn label<'a>(text: String, size: u16, color: Color) -> Element<'a, super::Message> {
+    widget::text(text)
+        .size(f32::from(size))
+        .class(cosmic::theme::Text::Color(color))
+        .into()
+}
+
+/// A button that can't be pressed.
+fn pill<'a>(
+    text: String,
+    background: Color,
+    on: Color,
+    radius: [f32; 4],
+) -> Element<'a, super::Message> {
+    label(text, 8, on)
+        .apply(widget::container)
+        .padding([1, 6])
+        .class(style::surface(background, radius))
+        .into()
+}
+
+fn dot<'a>(color: Color) -> Element<'a, super::Message> {
+    widget::Space::new(6.0, 6.0)
+        .apply(widget::container)
+        .class(style::surface(color, [3.0; 4]))
+        .into()
+}
+
+fn toggle<'a>(theme: &Theme, on: bool) -> Element<'a, super::Message> {
+    let (track, knob) = if on {
+        (theme.accent.base, theme.accent.on)
+    } else {
+        (theme.background.component.divider, theme.background.on)
+    };
+    widget::row()
+        .push_maybe(on.then(widget::horizontal_space))
+        .push(dot(knob.into()))
+        .push_maybe((!on).then(widget::horizontal_space))
+        .padding(2)
+        .width(20.0)
+        .apply(widget::container)
+        .class(style::surface(track.into(), [5.0; 4]))
+        .into()
+}
@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use cosmic::{
+    iced::{Alignment, Length},
+    widget::{self, tooltip},
+    Application, Apply, Element,
+};
+
+use crate::{
+    app::App,
+    core::{icons, style::destructive_button},
+    fl, Error,
+};
+
+/// A file in a themes directory that could not be read as a color scheme.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokenFile {
+    pub path: PathBuf,
+    pub error: String,
+    /// Where parsing stopped, for files that are not valid RON.
+    pub position: Option<ron::error::Position>,
+}
+
+impl BrokenFile {
+    pub fn new(path: PathBuf, error: Error) -> Self {
+        let position = match &error {
+            Error::Ron(e) => Some(e.position),
+            _ => None,
+        };
+        let error = match &error {
+            Error::Ron(e) => e.code.to_string(),
+            e => e.to_string(),
+        };
+        Self {
+            path,
+            error,
+            position,
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// The directory broken files are moved to, outside of any themes directory.
+pub fn quarantine_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(App::APP_ID).join("quarantine"))
+}
+
+/// Move a broken file out of the themes directory without deleting it.
+pub fn quarantine(path: &Path) -> Result<PathBuf, Error> {
+    let dir = quarantine_dir().ok_or(Error::DataDirNotFound)?;
+    std::fs::create_dir_all(&dir)?;
+    let file_name = path.file_name().ok_or(Error::ThemePathNotFound)?;
+    let mut target = dir.join(file_name);
+    let mut copy = 1;
+    while target.exists() {
+        target = dir.join(format!("{}.{copy}", file_name.to_string_lossy()));
+        copy += 1;
+    }
+    // Themes directories and the data directory can be on different file systems.
+    if std::fs::rename(path, &target).is_err() {
+        std::fs::copy(path, &target)?;
+        std::fs::remove_file(path)?;
+    }
+    Ok(target)
+}
+
+pub fn view<'a>(problems: &'a [BrokenFile]) -> Element<'a, super::Message> {
+    let spacing = cosmic::theme::spacing();
+    problems
+        .iter()
+        .fold(
+            widget::settings::section()
+                .title(fl!("problems"))
+                .add(widget::text::caption(fl!("problems-description"))),
+            |section, problem| {
+                let error = match problem.position {
+                    Some(position) => fl!(
+                        "problem-at",
+                        line = position.line,
+                        column = position.col,
+                        error = problem.error.clone()
+                    ),
+                    None => problem.error.clone(),
+                };
+                section.add(
+                    widget::row()
+                        .push(
+                            widget::column()
+                                .push(widget::text(problem.file_name()))
+                                .push(widget::text::caption(problem.path.display().to_string()))
+                                .push(widget::text::caption(error))
+                                .width(Length::Fill),
+                        )
+                        .push(widget::tooltip::tooltip(
+                            icons::get_handle("edit-symbolic", 14)
+                                .apply(widget::button::icon)
+                                .padding(spacing.space_xxs)
+                                .on_press(super::Message::OpenBrokenFile(problem.path.clone())),
+                            widget::text(fl!("open-file")),
+                            tooltip::Position::Bottom,
+                        ))
+                        .push(widget::tooltip::tooltip(
+                            icons::get_handle("arrow-into-box-symbolic", 14)
+                                .apply(widget::button::icon)
+                                .padding(spacing.space_xxs)
+                                .on_press(super::Message::QuarantineBrokenFile(
+                                    problem.path.clone(),
+                                )),
+                            widget::text(fl!("quarantine-file")),
+                            tooltip::Position::Bottom,
+                        ))
+                        .push(widget::tooltip::tooltip(
+                            icons::get_handle("user-trash-symbolic", 14)
+                                .apply(widget::button::icon)
+                                .class(destructive_button(cosmic::theme::active().cosmic().clone()))
+                                .padding(spacing.space_xxs)
+                                .on_press(super::Message::DeleteBrokenFile(problem.path.clone())),
+                            widget::text(fl!("delete-file")),
+                            tooltip::Position::Bottom,
+                        ))
+                        .align_y(Alignment::Center)
+                        .spacing(spacing.space_xxs),
+                )
+            },
+        )
+        .into()
+}
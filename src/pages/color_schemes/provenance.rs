@@ -0,0 +1,79 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use cosmic::Application;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{app::App, Error};
+
+use super::{config::ColorScheme, provider::ColorSchemeProvider};
+
+/// Where an installed color scheme came from.
+///
+/// Stored as a RON sidecar in the app data directory rather than next to the
+/// color scheme, so `themes/cosmic` only ever contains `ThemeBuilder` files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub author: Option<String>,
+    pub link: Option<String>,
+    pub provider: Option<ColorSchemeProvider>,
+    /// Id of the color scheme at its provider.
+    pub remote_id: Option<String>,
+    pub installed_at: Option<DateTime<Utc>>,
+    /// SHA-256 of the color scheme as installed, to tell when the provider has a newer version.
+    pub content_hash: Option<String>,
+}
+
+impl Provenance {
+    fn dir() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join(App::APP_ID).join("color-schemes"))
+    }
+
+    /// The sidecar for the color scheme installed at `path`, named after a hash of
+    /// the whole path so color schemes with the same name in different themes
+    /// directories keep their own.
+    fn sidecar(path: &Path) -> Option<PathBuf> {
+        let hash = Sha256::digest(path.as_os_str().as_encoded_bytes());
+        Some(Self::dir()?.join(format!("{hash:x}.ron")))
+    }
+
+    pub fn read(path: &Path) -> Option<Self> {
+        let sidecar = std::fs::read_to_string(Self::sidecar(path)?).ok()?;
+        ron::from_str(&sidecar)
+            .inspect_err(|e| {
+                log::error!("failed to read the provenance of {}: {e}", path.display())
+            })
+            .ok()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        let sidecar = Self::sidecar(path).ok_or(Error::DataDirNotFound)?;
+        if let Some(dir) = sidecar.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(sidecar, ron)?;
+        Ok(())
+    }
+
+    /// Move the sidecar along with a color scheme that was renamed.
+    pub fn rename(from: &Path, to: &Path) -> Result<(), Error> {
+        match (Self::sidecar(from), Self::sidecar(to)) {
+            (Some(from), Some(to)) if from.exists() => Ok(std::fs::rename(from, to)?),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn remove(path: &Path) -> Result<(), Error> {
+        match Self::sidecar(path) {
+            Some(sidecar) if sidecar.exists() => Ok(std::fs::remove_file(sidecar)?),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// SHA-256 of the color scheme in the format it is installed in.
+pub fn content_hash(color_scheme: &ColorScheme) -> Result<String, Error> {
+    Ok(format!("{:x}", Sha256::digest(color_scheme.to_ron()?)))
+}
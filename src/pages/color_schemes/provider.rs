@@ -0,0 +1,243 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+use super::{
+    config::ColorScheme,
+    cosmic_theme::CosmicTheme,
+    import::{self, TIMEOUT},
+    provenance::{self, Provenance},
+    query::Query,
+};
+
+const COSMIC_THEMES_URL: &str = "https://cosmic-themes.org/api/themes/";
+
+/// Indexes carry every color scheme of a provider, far more than a single one.
+const MAX_INDEX: usize = 64 * 1024 * 1024;
+
+/// Where available color schemes are fetched from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorSchemeProvider {
+    #[default]
+    CosmicThemes,
+    /// A JSON index in the same shape as the cosmic-themes.org API, over `https://` or `file://`.
+    JsonIndex { name: String, url: String },
+    /// A directory of RON color schemes, or of an `index.json` in the cosmic-themes.org shape.
+    Local { name: String, path: PathBuf },
+}
+
+impl ColorSchemeProvider {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::CosmicThemes => "cosmic-themes.org",
+            Self::JsonIndex { name, .. } | Self::Local { name, .. } => name,
+        }
+    }
+
+    pub async fn fetch(
+        self,
+        query: Query,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ColorScheme>, Error> {
+        let color_schemes = match self {
+            Self::CosmicThemes => {
+                let response = client()?
+                    .get(COSMIC_THEMES_URL)
+                    .query(&query.params(limit, offset))
+                    .send()
+                    .await?;
+                let themes: Vec<CosmicTheme> = response.json().await?;
+                return Ok(themes.into_iter().map(ColorScheme::from).collect());
+            }
+            provider => provider.list().await?,
+        };
+
+        // Only the cosmic-themes.org API understands the query, filter everything else here.
+        let mut color_schemes: Vec<ColorScheme> = color_schemes
+            .into_iter()
+            .filter(|color_scheme| query.matches(color_scheme))
+            .collect();
+        query.sort(&mut color_schemes);
+
+        Ok(color_schemes.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// Every color scheme of a provider without a query API, read from its index
+    /// or directory in one go. cosmic-themes.org is only ever asked for pages.
+    async fn list(self) -> Result<Vec<ColorScheme>, Error> {
+        match self {
+            Self::CosmicThemes => Ok(vec![]),
+            Self::JsonIndex { url, .. } => {
+                let parsed = Url::parse(&url).map_err(|_| Error::UnsupportedUrl(url.clone()))?;
+                if parsed.scheme() == "file" {
+                    let path = parsed
+                        .to_file_path()
+                        .map_err(|_| Error::UnsupportedUrl(url))?;
+                    return read_index(&path).await;
+                }
+                let response = client()?.get(parsed).send().await?.error_for_status()?;
+                let index = import::read_capped(response, MAX_INDEX).await?;
+                let themes: Vec<CosmicTheme> = serde_json::from_str(&index)?;
+                Ok(themes.into_iter().map(ColorScheme::from).collect())
+            }
+            Self::Local { path, .. } => {
+                let index = path.join("index.json");
+                if tokio::fs::try_exists(&index).await.unwrap_or(false) {
+                    read_index(&index).await
+                } else {
+                    read_directory(&path).await
+                }
+            }
+        }
+    }
+
+    /// The color scheme with `id` at the provider, `None` if the provider doesn't have it.
+    /// cosmic-themes.org is asked for that one color scheme, other providers are
+    /// listed once per check and looked up in `listed`.
+    async fn find(
+        &self,
+        id: &str,
+        listed: &mut HashMap<ColorSchemeProvider, HashMap<String, ColorScheme>>,
+    ) -> Result<Option<ColorScheme>, Error> {
+        if *self == Self::CosmicThemes {
+            let response = client()?
+                .get(format!("{COSMIC_THEMES_URL}{id}/"))
+                .send()
+                .await?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let theme: CosmicTheme = response.error_for_status()?.json().await?;
+            return Ok(Some(ColorScheme::from(theme)));
+        }
+
+        if !listed.contains_key(self) {
+            let remote = self.clone().list().await;
+            // Kept even when listing failed, so the provider isn't asked again for
+            // every color scheme that came from it.
+            let by_id = remote
+                .as_ref()
+                .map(|remote| {
+                    remote
+                        .iter()
+                        .filter_map(|remote| {
+                            let id = remote.provenance.as_ref()?.remote_id.clone()?;
+                            Some((id, remote.clone()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            listed.insert(self.clone(), by_id);
+            remote?;
+        }
+        Ok(listed.get(self).and_then(|by_id| by_id.get(id)).cloned())
+    }
+}
+
+/// What the provider of an installed color scheme says about it.
+#[derive(Debug, Clone)]
+pub enum Update {
+    /// The provider has a newer version.
+    Available(ColorScheme),
+    /// The color scheme was installed without an id at its provider, so it cannot be found there.
+    CannotCheck,
+}
+
+/// Look up installed color schemes by their id at the provider they came from,
+/// keyed by the installed name. Up to date color schemes are left out.
+pub async fn check_updates(installed: Vec<ColorScheme>) -> Vec<(String, Update)> {
+    let mut updates = vec![];
+    let mut listed = HashMap::new();
+
+    for color_scheme in installed {
+        let Some(provenance) = color_scheme.provenance else {
+            continue;
+        };
+        let (Some(provider), Some(content_hash)) = (provenance.provider, provenance.content_hash)
+        else {
+            continue;
+        };
+        let Some(remote_id) = provenance.remote_id else {
+            updates.push((color_scheme.name, Update::CannotCheck));
+            continue;
+        };
+
+        let remote = match provider.find(&remote_id, &mut listed).await {
+            Ok(Some(remote)) => remote,
+            Ok(None) => continue,
+            Err(e) => {
+                log::error!("failed to check {} for updates: {e}", color_scheme.name);
+                continue;
+            }
+        };
+
+        match provenance::content_hash(&remote) {
+            Ok(hash) if hash != content_hash => {
+                updates.push((color_scheme.name, Update::Available(remote)))
+            }
+            Ok(_) => (),
+            Err(e) => log::error!("failed to hash {}: {e}", remote.name),
+        }
+    }
+
+    updates
+}
+
+fn client() -> Result<reqwest::Client, Error> {
+    Ok(reqwest::Client::builder().timeout(TIMEOUT).build()?)
+}
+
+async fn read_index(path: &Path) -> Result<Vec<ColorScheme>, Error> {
+    let index = tokio::fs::read_to_string(path).await?;
+    let themes: Vec<CosmicTheme> = serde_json::from_str(&index)?;
+    Ok(themes.into_iter().map(ColorScheme::from).collect())
+}
+
+async fn read_directory(path: &Path) -> Result<Vec<ColorScheme>, Error> {
+    let mut color_schemes = vec![];
+    let mut read_dir = tokio::fs::read_dir(path).await?;
+
+    while let Some(entry) = read_dir.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("ron") {
+            continue;
+        }
+
+        let ron = tokio::fs::read_to_string(&path).await?;
+        let (theme, variant) = match ColorScheme::parse(&ron) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                log::error!("failed to parse color scheme {}: {e}", path.display());
+                continue;
+            }
+        };
+
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        color_schemes.push(ColorScheme {
+            // The file name is what finds the color scheme again when checking for updates.
+            provenance: Some(Provenance {
+                remote_id: Some(name.clone()),
+                ..Default::default()
+            }),
+            name,
+            path: None,
+            link: Some(format!("file://{}", path.display())),
+            author: None,
+            theme,
+            variant,
+        });
+    }
+
+    Ok(color_schemes)
+}
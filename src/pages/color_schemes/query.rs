@@ -0,0 +1,218 @@
+use crate::fl;
+
+use super::config::ColorScheme;
+
+/// Search, sort and filter options for the available color schemes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Query {
+    pub search: String,
+    pub sort: SortOrder,
+    pub mode: ModeFilter,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    #[default]
+    Name,
+    Newest,
+    MostDownloaded,
+    Author,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ModeFilter {
+    #[default]
+    All,
+    Dark,
+    Light,
+}
+
+impl Query {
+    /// Query string parameters for the cosmic-themes.org API.
+    pub fn params(&self, limit: usize, offset: usize) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("order", self.sort.param().to_string()),
+            ("limit", limit.to_string()),
+            ("offset", offset.to_string()),
+        ];
+        if !self.search.trim().is_empty() {
+            params.push(("search", self.search.trim().to_string()));
+        }
+        if let Some(mode) = self.mode.param() {
+            params.push(("mode", mode.to_string()));
+        }
+        params
+    }
+
+    /// Apply the search and mode filter locally, for providers without a query API.
+    pub fn matches(&self, color_scheme: &ColorScheme) -> bool {
+        let search = self.search.trim().to_lowercase();
+        let found = search.is_empty()
+            || color_scheme.name.to_lowercase().contains(&search)
+            || color_scheme
+                .author
+                .as_ref()
+                .is_some_and(|author| author.to_lowercase().contains(&search));
+        found && self.mode.matches(color_scheme)
+    }
+
+    /// Sort locally, providers without a query API have no download counts or dates,
+    /// so those orders keep the order of the index.
+    pub fn sort(&self, color_schemes: &mut [ColorScheme]) {
+        match self.sort {
+            SortOrder::Name => color_schemes.sort_by_key(|c| c.name.to_lowercase()),
+            SortOrder::Author => color_schemes.sort_by_key(|c| c.author.clone()),
+            SortOrder::Newest | SortOrder::MostDownloaded => (),
+        }
+    }
+}
+
+impl SortOrder {
+    pub fn all() -> &'static [Self] {
+        &[Self::Name, Self::Newest, Self::MostDownloaded, Self::Author]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Name => fl!("sort-name"),
+            Self::Newest => fl!("sort-newest"),
+            Self::MostDownloaded => fl!("sort-most-downloaded"),
+            Self::Author => fl!("sort-author"),
+        }
+    }
+
+    fn param(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Newest => "-created",
+            Self::MostDownloaded => "-downloads",
+            Self::Author => "author",
+        }
+    }
+}
+
+impl ModeFilter {
+    pub fn all() -> &'static [Self] {
+        &[Self::All, Self::Dark, Self::Light]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::All => fl!("all"),
+            Self::Dark => fl!("dark"),
+            Self::Light => fl!("light"),
+        }
+    }
+
+    fn param(&self) -> Option<&'static str> {
+        match self {
+            Self::All => None,
+            Self::Dark => Some("dark"),
+            Self::Light => Some("light"),
+        }
+    }
+
+    /// Whether a color scheme has a variant for this mode.
+    pub fn matches(&self, color_scheme: &ColorScheme) -> bool {
+        let is_dark = color_scheme.theme.palette.is_dark();
+        let paired = color_scheme.variant.is_some();
+        match self {
+            Self::All => true,
+            Self::Dark => is_dark || paired,
+            Self::Light => !is_dark || paired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmic::cosmic_theme::ThemeBuilder;
+
+    use super::*;
+
+    fn color_scheme(name: &str, author: Option<&str>, theme: ThemeBuilder) -> ColorScheme {
+        ColorScheme {
+            name: name.to_string(),
+            author: author.map(str::to_string),
+            theme,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn params_skip_empty_search_and_all_modes() {
+        let query = Query {
+            search: "  ".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            query.params(15, 30),
+            vec![
+                ("order", "name".to_string()),
+                ("limit", "15".to_string()),
+                ("offset", "30".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn params_include_trimmed_search_and_mode() {
+        let query = Query {
+            search: " nord ".to_string(),
+            sort: SortOrder::MostDownloaded,
+            mode: ModeFilter::Light,
+        };
+        assert_eq!(
+            query.params(10, 0),
+            vec![
+                ("order", "-downloads".to_string()),
+                ("limit", "10".to_string()),
+                ("offset", "0".to_string()),
+                ("search", "nord".to_string()),
+                ("mode", "light".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_name_or_author_ignoring_case() {
+        let query = Query {
+            search: "NORD".to_string(),
+            ..Default::default()
+        };
+        let nord = color_scheme("Nord", None, ThemeBuilder::dark());
+        let by_nord = color_scheme("Frost", Some("nordtheme"), ThemeBuilder::dark());
+        let other = color_scheme("Dracula", Some("zeno"), ThemeBuilder::dark());
+        assert!(query.matches(&nord));
+        assert!(query.matches(&by_nord));
+        assert!(!query.matches(&other));
+    }
+
+    #[test]
+    fn mode_filter_keeps_paired_color_schemes() {
+        let dark = color_scheme("Dark", None, ThemeBuilder::dark());
+        let light = color_scheme("Light", None, ThemeBuilder::light());
+        let paired = ColorScheme {
+            variant: Some(ThemeBuilder::light()),
+            ..color_scheme("Paired", None, ThemeBuilder::dark())
+        };
+        assert!(ModeFilter::Dark.matches(&dark));
+        assert!(!ModeFilter::Dark.matches(&light));
+        assert!(ModeFilter::Light.matches(&light));
+        assert!(!ModeFilter::Light.matches(&dark));
+        assert!(ModeFilter::Light.matches(&paired));
+        assert!(ModeFilter::Dark.matches(&paired));
+    }
+
+    #[test]
+    fn sort_by_name_ignores_case() {
+        let mut color_schemes = vec![
+            color_scheme("b", None, ThemeBuilder::dark()),
+            color_scheme("C", None, ThemeBuilder::dark()),
+            color_scheme("A", None, ThemeBuilder::dark()),
+        ];
+        Query::default().sort(&mut color_schemes);
+        let names: Vec<&str> = color_schemes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["A", "b", "C"]);
+    }
+}
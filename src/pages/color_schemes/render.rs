@@ -0,0 +1,265 @@
+//! Headless rendering of color scheme previews to PNG with a CPU rasterizer.
+//!
+//! Draws the same mock window as the preview cards. Text is drawn as bars in
+//! the text colors, so no fonts are needed.
+
+use std::path::PathBuf;
+
+use cosmic::cosmic_theme::{palette::Srgba, Theme, ThemeBuilder};
+use tiny_skia::{FillRule, Paint, Path, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+use crate::Error;
+
+use super::config::ColorScheme;
+
+/// Size of one mock window in layout units, scaled to the requested width.
+const WINDOW_WIDTH: f32 = 240.0;
+const WINDOW_HEIGHT: f32 = 150.0;
+const MARGIN: f32 = 8.0;
+
+pub const DEFAULT_WIDTH: u32 = 512;
+/// Wider images take hundreds of megabytes and may fail to allocate at all.
+pub const MAX_WIDTH: u32 = 8192;
+
+/// Render a color scheme, and its opposite variant beside it, to PNG bytes.
+pub fn render(color_scheme: &ColorScheme, width: u32) -> Result<Vec<u8>, Error> {
+    if !(1..=MAX_WIDTH).contains(&width) {
+        return Err(Error::Render("the width must be between 1 and 8192 pixels"));
+    }
+    let themes: Vec<Theme> = std::iter::once(&color_scheme.theme)
+        .chain(color_scheme.variant.as_ref())
+        .cloned()
+        .map(ThemeBuilder::build)
+        .collect();
+
+    let units_width = MARGIN + themes.len() as f32 * (WINDOW_WIDTH + MARGIN);
+    let units_height = WINDOW_HEIGHT + 2.0 * MARGIN;
+    let scale = width as f32 / units_width;
+    let height = (units_height * scale).round() as u32;
+
+    let mut pixmap = Pixmap::new(width, height).ok_or(Error::Render("invalid image size"))?;
+    let mut canvas = Canvas {
+        pixmap: &mut pixmap,
+        transform: Transform::from_scale(scale, scale),
+    };
+
+    canvas.fill(
+        rect(0.0, 0.0, units_width, units_height),
+        [0.0; 4],
+        themes[0].background.base,
+    );
+    for (index, theme) in themes.iter().enumerate() {
+        let x = MARGIN + index as f32 * (WINDOW_WIDTH + MARGIN);
+        canvas.window(theme, x, MARGIN);
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|_| Error::Render("failed to encode the PNG"))
+}
+
+/// Render a color scheme and write the PNG to `path`, off the UI thread.
+pub async fn save(color_scheme: ColorScheme, path: PathBuf, width: u32) -> Result<PathBuf, Error> {
+    tokio::task::spawn_blocking(move || {
+        std::fs::write(&path, render(&color_scheme, width)?)?;
+        Ok(path)
+    })
+    .await
+    .map_err(|e| Error::Io(std::io::Error::other(e)))?
+}
+
+struct Canvas<'a> {
+    pixmap: &'a mut Pixmap,
+    transform: Transform,
+}
+
+impl Canvas<'_> {
+    /// The mock window of `preview::mock_window`, with its top left corner at `x`, `y`.
+    fn window(&mut self, theme: &Theme, x: f32, y: f32) {
+        let radii = &theme.corner_radii;
+        let (width, height) = (WINDOW_WIDTH, WINDOW_HEIGHT);
+
+        self.fill(
+            rect(x, y, width, height),
+            radii.radius_s,
+            theme.background.base,
+        );
+        self.stroke(
+            rect(x, y, width, height),
+            radii.radius_s,
+            theme.background.divider,
+        );
+
+        // Header bar with the window controls.
+        let header = 18.0;
+        self.fill(
+            rect(x, y, width, header),
+            [radii.radius_s[0], radii.radius_s[1], 0.0, 0.0],
+            theme.background.component.base,
+        );
+        self.text(x + 8.0, y + 7.0, 40.0, 4.0, theme.background.on);
+        for (index, color) in [
+            theme.background.component.on,
+            theme.background.component.on,
+            theme.accent.base,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let cx = x + width - 30.0 + index as f32 * 10.0;
+            self.fill(rect(cx, y + 6.0, 6.0, 6.0), [3.0; 4], color);
+        }
+
+        // Sidebar with a selected row.
+        let body_y = y + header + 6.0;
+        let sidebar_width = 64.0;
+        let body_height = height - header - 12.0;
+        self.fill(
+            rect(x + 6.0, body_y, sidebar_width, body_height),
+            radii.radius_s,
+            theme.primary.base,
+        );
+        let mut selected = theme.accent.base;
+        selected.alpha = 0.2;
+        self.fill(
+            rect(x + 9.0, body_y + 3.0, sidebar_width - 6.0, 12.0),
+            radii.radius_s,
+            selected,
+        );
+        self.text(x + 13.0, body_y + 7.0, 32.0, 4.0, theme.accent.base);
+        self.text(x + 13.0, body_y + 22.0, 26.0, 4.0, theme.primary.on);
+        self.text(x + 13.0, body_y + 34.0, 36.0, 4.0, theme.primary.on);
+
+        // Text at each level.
+        let content_x = x + sidebar_width + 14.0;
+        let content_width = width - sidebar_width - 20.0;
+        let mut secondary = theme.background.on;
+        secondary.alpha = 0.7;
+        self.text(content_x, body_y + 2.0, 70.0, 7.0, theme.background.on);
+        self.text(
+            content_x,
+            body_y + 16.0,
+            content_width - 10.0,
+            4.0,
+            secondary,
+        );
+        self.text(
+            content_x,
+            body_y + 24.0,
+            content_width - 40.0,
+            4.0,
+            secondary,
+        );
+        self.text(content_x, body_y + 34.0, 30.0, 4.0, theme.accent.base);
+
+        // Suggested, standard and destructive buttons.
+        let button_y = body_y + 46.0;
+        let mut button_x = content_x;
+        for component in [
+            &theme.accent_button,
+            &theme.button,
+            &theme.destructive_button,
+        ] {
+            self.fill(
+                rect(button_x, button_y, 42.0, 16.0),
+                radii.radius_xl,
+                component.base,
+            );
+            self.text(button_x + 9.0, button_y + 6.0, 24.0, 4.0, component.on);
+            button_x += 48.0;
+        }
+
+        // Text input and toggles.
+        let input_y = button_y + 24.0;
+        let input_width = content_width - 60.0;
+        let input = rect(content_x, input_y, input_width, 16.0);
+        self.fill(input, radii.radius_s, theme.background.component.base);
+        self.stroke(input, radii.radius_s, theme.accent.base);
+        let mut placeholder = theme.background.component.on;
+        placeholder.alpha = 0.6;
+        self.text(content_x + 6.0, input_y + 6.0, 40.0, 4.0, placeholder);
+
+        let toggle_x = content_x + input_width + 6.0;
+        for (index, on) in [true, false].into_iter().enumerate() {
+            let tx = toggle_x + index as f32 * 26.0;
+            let (track, knob) = if on {
+                (theme.accent.base, theme.accent.on)
+            } else {
+                (theme.background.component.divider, theme.background.on)
+            };
+            self.fill(rect(tx, input_y + 3.0, 22.0, 10.0), [5.0; 4], track);
+            let knob_x = if on { tx + 14.0 } else { tx + 2.0 };
+            self.fill(rect(knob_x, input_y + 5.0, 6.0, 6.0), [3.0; 4], knob);
+        }
+    }
+
+    /// A line of text, drawn as a rounded bar.
+    fn text(&mut self, x: f32, y: f32, width: f32, height: f32, color: Srgba) {
+        self.fill(rect(x, y, width, height), [height / 2.0; 4], color);
+    }
+
+    fn fill(&mut self, rect: Option<Rect>, radius: [f32; 4], color: Srgba) {
+        let Some(path) = rect.and_then(|rect| rounded_rect(rect, radius)) else {
+            return;
+        };
+        self.pixmap.fill_path(
+            &path,
+            &paint(color),
+            FillRule::Winding,
+            self.transform,
+            None,
+        );
+    }
+
+    fn stroke(&mut self, rect: Option<Rect>, radius: [f32; 4], color: Srgba) {
+        let Some(path) = rect.and_then(|rect| rounded_rect(rect, radius)) else {
+            return;
+        };
+        let stroke = Stroke {
+            width: 1.0,
+            ..Stroke::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint(color), &stroke, self.transform, None);
+    }
+}
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Option<Rect> {
+    Rect::from_xywh(x, y, width, height)
+}
+
+fn paint(color: Srgba) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+        channel(color.alpha),
+    );
+    paint.anti_alias = true;
+    paint
+}
+
+fn channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// A rectangle with corner radii in the order top left, top right, bottom right, bottom left.
+fn rounded_rect(rect: Rect, radius: [f32; 4]) -> Option<Path> {
+    let max = rect.width().min(rect.height()) / 2.0;
+    let [top_left, top_right, bottom_right, bottom_left] = radius.map(|r| r.clamp(0.0, max));
+    let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+
+    let mut path = PathBuilder::new();
+    path.move_to(left + top_left, top);
+    path.line_to(right - top_right, top);
+    path.quad_to(right, top, right, top + top_right);
+    path.line_to(right, bottom - bottom_right);
+    path.quad_to(right, bottom, right - bottom_right, bottom);
+    path.line_to(left + bottom_left, bottom);
+    path.quad_to(left, bottom, left, bottom - bottom_left);
+    path.line_to(left, top + top_left);
+    path.quad_to(left, top, left + top_left, top);
+    path.close();
+    path.finish()
+}
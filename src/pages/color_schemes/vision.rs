@@ -0,0 +1,178 @@
+use cosmic::cosmic_theme::{palette::Srgba, Component, Container, Theme};
+
+use crate::{core::color::transform_linear, fl};
+
+use super::cache::Built;
+
+/// Color vision deficiencies the preview cards can be simulated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+impl Deficiency {
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Protanopia,
+            Self::Deuteranopia,
+            Self::Tritanopia,
+            Self::Achromatopsia,
+        ]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Protanopia => fl!("protanopia"),
+            Self::Deuteranopia => fl!("deuteranopia"),
+            Self::Tritanopia => fl!("tritanopia"),
+            Self::Achromatopsia => fl!("achromatopsia"),
+        }
+    }
+
+    /// Simulation matrix in linear RGB, from Machado, Oliveira and Fernandes (2009)
+    /// at full severity, and the Rec. 709 luminance for achromatopsia.
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+            Self::Achromatopsia => [[0.2126, 0.7152, 0.0722]; 3],
+        }
+    }
+
+    /// How the colors of a built color scheme look with this deficiency.
+    pub fn simulate(&self, built: &Built) -> Built {
+        Built::of(
+            self.simulate_theme(&built.theme),
+            built
+                .variant
+                .as_ref()
+                .map(|theme| self.simulate_theme(theme)),
+        )
+    }
+
+    /// Simulate the container and component colors of a theme, the ones widgets draw with.
+    fn simulate_theme(&self, theme: &Theme) -> Theme {
+        let matrix = self.matrix();
+        let simulate = |color: &mut Srgba| *color = transform_linear(*color, &matrix);
+        let mut theme = theme.clone();
+
+        for container in [
+            &mut theme.background,
+            &mut theme.primary,
+            &mut theme.secondary,
+        ] {
+            simulate_container(container, &simulate);
+        }
+        for component in [
+            &mut theme.accent,
+            &mut theme.success,
+            &mut theme.destructive,
+            &mut theme.warning,
+            &mut theme.accent_button,
+            &mut theme.success_button,
+            &mut theme.destructive_button,
+            &mut theme.warning_button,
+            &mut theme.icon_button,
+            &mut theme.link_button,
+            &mut theme.text_button,
+            &mut theme.button,
+        ] {
+            simulate_component(component, &simulate);
+        }
+        theme
+    }
+}
+
+fn simulate_container(container: &mut Container, simulate: &impl Fn(&mut Srgba)) {
+    simulate(&mut container.base);
+    simulate(&mut container.divider);
+    simulate(&mut container.on);
+    simulate_component(&mut container.component, simulate);
+}
+
+fn simulate_component(component: &mut Component, simulate: &impl Fn(&mut Srgba)) {
+    for color in [
+        &mut component.base,
+        &mut component.hover,
+        &mut component.pressed,
+        &mut component.selected,
+        &mut component.selected_text,
+        &mut component.focus,
+        &mut component.divider,
+        &mut component.on,
+        &mut component.disabled,
+        &mut component.on_disabled,
+        &mut component.border,
+        &mut component.disabled_border,
+    ] {
+        simulate(color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::color::relative_luminance;
+
+    #[test]
+    fn matrices_keep_grays() {
+        for deficiency in Deficiency::all() {
+            for row in deficiency.matrix() {
+                let sum: f32 = row.iter().sum();
+                assert!(
+                    (sum - 1.0).abs() < 0.001,
+                    "{deficiency:?} row sums to {sum}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn achromatopsia_keeps_luminance_only() {
+        let matrix = Deficiency::Achromatopsia.matrix();
+        for color in [
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            Srgba::new(0.1, 0.7, 0.3, 1.0),
+            Srgba::new(0.4, 0.2, 0.9, 1.0),
+        ] {
+            let simulated = transform_linear(color, &matrix);
+            assert_eq!(simulated.red, simulated.green);
+            assert_eq!(simulated.green, simulated.blue);
+            let (expected, actual) = (relative_luminance(color), relative_luminance(simulated));
+            assert!((expected - actual).abs() < 0.001, "{expected} != {actual}");
+        }
+    }
+
+    #[test]
+    fn red_green_deficiencies_confuse_red_and_green() {
+        let (red, green) = (
+            Srgba::new(0.8, 0.2, 0.2, 1.0),
+            Srgba::new(0.2, 0.6, 0.2, 1.0),
+        );
+        let distance = |a: Srgba, b: Srgba| (a.red - b.red).abs() + (a.green - b.green).abs();
+        for deficiency in [Deficiency::Protanopia, Deficiency::Deuteranopia] {
+            let matrix = deficiency.matrix();
+            let simulated = distance(
+                transform_linear(red, &matrix),
+                transform_linear(green, &matrix),
+            );
+            assert!(simulated < distance(red, green), "{deficiency:?}");
+        }
+    }
+}
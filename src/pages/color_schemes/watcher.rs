@@ -0,0 +1,55 @@
+use std::{any::TypeId, time::Duration};
+
+use cosmic::iced::{futures::SinkExt, stream, Subscription};
+use notify::{RecursiveMode, Watcher};
+
+use super::{config::ColorScheme, Message};
+
+/// How long the themes directories have to stay quiet before reloading, so
+/// copying many files at once or a dotfile sync causes a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Reload the installed color schemes whenever a file in any of the themes
+/// directories `ColorScheme::scan` reads is created, changed or removed.
+pub fn subscription() -> Subscription<Message> {
+    struct ThemesWatcher;
+
+    Subscription::run_with_id(
+        TypeId::of::<ThemesWatcher>(),
+        stream::channel(1, |mut output| async move {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let watcher = notify::recommended_watcher(
+                move |event: notify::Result<notify::Event>| match event {
+                    Ok(event) if !event.kind.is_access() => {
+                        let _ = tx.send(());
+                    }
+                    Ok(_) => (),
+                    Err(e) => log::error!("failed to watch the themes directories: {e}"),
+                },
+            );
+
+            match watcher {
+                Ok(mut watcher) => {
+                    for dir in ColorScheme::themes_dirs() {
+                        if !dir.exists() {
+                            continue;
+                        }
+                        if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                            log::error!("failed to watch {}: {e}", dir.display());
+                        }
+                    }
+
+                    while rx.recv().await.is_some() {
+                        while let Ok(Some(())) = tokio::time::timeout(DEBOUNCE, rx.recv()).await {}
+                        if output.send(Message::ReloadColorSchemes).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => log::error!("failed to watch the themes directories: {e}"),
+            }
+
+            std::future::pending::<()>().await;
+        }),
+    )
+}
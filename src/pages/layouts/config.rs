@@ -2,9 +2,9 @@ use super::{
     preview::{LayoutPreview, PanelProperties, Position},
     Message,
 };
-use crate::core::resources;
+use crate::{core::resources, Error};
 use cosmic::{widget, Element};
-use cosmic_ext_config_templates::Schema;
+use cosmic_ext_config_templates::{load_template, Schema};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Clone, Deserialize, PartialEq)]
@@ -68,7 +68,22 @@ impl Layout {
         }
     }
 
+    pub fn apply(&self) -> Result<(), Error> {
+        load_template(self.schema()).map_err(|e| Error::Template(e.to_string()))
+    }
+
     pub(crate) fn list() -> Vec<Layout> {
         Vec::from([Layout::Cosmic, Layout::Mac, Layout::Windows, Layout::Ubuntu])
     }
+
+    /// Find a layout by its display name or variant name, ignoring case.
+    pub(crate) fn find(name: &str) -> Result<Layout, Error> {
+        Self::list()
+            .into_iter()
+            .find(|layout| {
+                layout.name().eq_ignore_ascii_case(name)
+                    || format!("{layout:?}").eq_ignore_ascii_case(name)
+            })
+            .ok_or_else(|| Error::NotFound("layout", name.to_string()))
+    }
 }
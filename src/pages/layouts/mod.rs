@@ -1,6 +1,5 @@
 use config::Layout;
 use cosmic::{iced::alignment::Horizontal, widget, Apply, Element, Task};
-use cosmic_ext_config_templates::load_template;
 
 use crate::fl;
 
@@ -59,7 +58,7 @@ impl Layouts {
         match message {
             Message::ApplyLayout(layout) => {
                 self.selected_layout = Some(layout.clone());
-                if let Err(e) = load_template(layout.schema().clone()) {
+                if let Err(e) = layout.apply() {
                     eprintln!("Failed to load template: {}", e);
                 }
             }
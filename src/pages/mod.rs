@@ -4,6 +4,7 @@ pub mod layouts;
 pub mod panel;
 pub mod shortcuts;
 pub mod snapshots;
+pub mod style;
 
 pub use color_schemes::ColorSchemes;
 pub use dock::Dock;
@@ -11,3 +12,4 @@ pub use layouts::Layouts;
 pub use panel::Panel;
 pub use shortcuts::Shortcuts;
 pub use snapshots::Snapshots;
+pub use style::Style;
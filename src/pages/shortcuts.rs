@@ -6,17 +6,17 @@ use cosmic::{
     Element, Task,
 };
 
-use crate::fl;
+use crate::{fl, Error};
 
 pub struct Shortcuts {}
 
 #[derive(Debug, Clone)]
-enum Shortcut {
+pub enum Shortcut {
     Windows,
 }
 
 impl Shortcut {
-    fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'static str {
         match self {
             Shortcut::Windows => "Windows",
         }
@@ -33,10 +33,23 @@ impl Shortcut {
             Self::Windows => include_str!("../../res/shortcuts/windows.ron"),
         }
     }
+
+    pub fn list() -> Vec<Shortcut> {
+        vec![Shortcut::Windows]
+    }
+
+    /// Replace the custom shortcuts with this preset.
+    pub fn apply(&self) -> Result<(), Error> {
+        let home = env::var("HOME").map_err(|_| Error::DataDirNotFound)?;
+        let path =
+            Path::new(&home).join(".config/cosmic/com.system76.CosmicSettings.Shortcuts/v1/custom");
+
+        fs::write(&path, self.schema())?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
-#[allow(private_interfaces)]
 pub enum Message {
     ApplyShortcuts(Shortcut),
 }
@@ -49,10 +62,7 @@ impl Shortcuts {
     pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
         match message {
             Message::ApplyShortcuts(shortcut) => {
-                let path = Path::new(&env::var("HOME").unwrap())
-                    .join(".config/cosmic/com.system76.CosmicSettings.Shortcuts/v1/custom");
-
-                if let Err(e) = fs::write(&path, shortcut.schema()) {
+                if let Err(e) = shortcut.apply() {
                     eprintln!("Failed to write shortcuts: {}", e);
                 }
             }
@@ -3,7 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{app::App, fl};
+use crate::{app::App, fl, Error};
 use chrono::{NaiveDateTime, Utc};
 use cosmic::{
     cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, Config, CosmicConfigEntry},
@@ -81,7 +81,7 @@ impl Snapshot {
         self.created.format("%Y-%m-%d %H:%M:%S").to_string()
     }
 
-    pub fn schema(&self) -> Schema {
-        Schema::from_file(&self.path).unwrap()
+    pub fn schema(&self) -> Result<Schema, Error> {
+        Schema::from_file(&self.path).map_err(|e| Error::Template(e.to_string()))
     }
 }
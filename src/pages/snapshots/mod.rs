@@ -3,7 +3,7 @@ use cosmic::{iced::Length, widget, Application, Apply, Element, Task};
 use cosmic_ext_config_templates::{load_template, panel::PanelSchema, Schema};
 use dirs::data_local_dir;
 
-use crate::{app::App, core::icons, fl};
+use crate::{app::App, core::icons, fl, Error};
 
 pub mod config;
 
@@ -132,77 +132,79 @@ impl Snapshots {
         let mut commands = vec![];
         match message {
             Message::RestoreSnapshot(snapshot) => {
-                if let Err(e) = load_template(snapshot.schema().clone()) {
-                    eprintln!("Failed to load template: {}", e);
+                if let Err(e) = Self::restore(&snapshot) {
+                    log::error!("Failed to restore snapshot: {}", e);
                 }
             }
             Message::OpenSaveDialog => commands.push(self.update(Message::OpenSaveDialog)),
             Message::CreateSnapshot(name, kind) => {
-                let path = data_local_dir()
-                    .unwrap()
-                    .join(App::APP_ID)
-                    .join("snapshots");
-                if !path.exists() {
-                    if let Err(e) = std::fs::create_dir_all(&path) {
-                        log::error!("{e}");
-                    }
-                }
-                let snapshot = Snapshot::new(&name, &path, kind);
-                match PanelSchema::generate()
-                    .and_then(|panel_schema| Schema::Panel(panel_schema).save(&snapshot.path))
-                {
-                    Ok(_) => {
-                        let mut snapshots = self.config.snapshots.clone();
-                        snapshots.push(snapshot.clone());
-                        snapshots.sort_by(|a, b| {
-                            b.created
-                                .and_utc()
-                                .timestamp()
-                                .cmp(&a.created.and_utc().timestamp())
-                        });
-                        match self
-                            .config
-                            .set_snapshots(&SnapshotsConfig::helper(), snapshots)
-                        {
-                            Ok(written) => {
-                                if !written {
-                                    log::error!("Failed to write snapshots to config");
-                                }
-                            }
-                            Err(e) => log::error!("Failed to set snapshots: {}", e),
-                        }
-                    }
-                    Err(e) => log::error!("Failed to generate template: {}", e),
+                if let Err(e) = self.create(&name, kind) {
+                    log::error!("Failed to create snapshot: {}", e);
                 }
             }
             Message::DeleteSnapshot(snapshot) => {
-                if snapshot.path.exists() {
-                    if let Err(e) = std::fs::remove_file(&snapshot.path) {
-                        log::error!("Failed to delete layout: {}", e);
-                        return Task::batch(commands);
-                    }
-                }
-                let mut snapshots = self.config.snapshots.clone();
-                snapshots.retain(|l| *l != snapshot);
-                snapshots.sort_by(|a, b| {
-                    b.created
-                        .and_utc()
-                        .timestamp()
-                        .cmp(&a.created.and_utc().timestamp())
-                });
-                match self
-                    .config
-                    .set_snapshots(&SnapshotsConfig::helper(), snapshots)
-                {
-                    Ok(written) => {
-                        if !written {
-                            log::error!("Failed to write snapshots to config");
-                        }
-                    }
-                    Err(e) => log::error!("Failed to set snapshots: {}", e),
+                if let Err(e) = self.delete(&snapshot) {
+                    log::error!("Failed to delete snapshot: {}", e);
                 }
             }
         }
         Task::batch(commands)
     }
+
+    pub fn find(&self, name: &str) -> Result<Snapshot, Error> {
+        self.config
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.name == name)
+            .cloned()
+            .ok_or_else(|| Error::NotFound("snapshot", name.to_string()))
+    }
+
+    pub fn create(&mut self, name: &str, kind: SnapshotKind) -> Result<Snapshot, Error> {
+        let path = data_local_dir()
+            .ok_or(Error::DataDirNotFound)?
+            .join(App::APP_ID)
+            .join("snapshots");
+        if !path.exists() {
+            std::fs::create_dir_all(&path)?;
+        }
+        let snapshot = Snapshot::new(name, &path, kind);
+        PanelSchema::generate()
+            .and_then(|panel_schema| Schema::Panel(panel_schema).save(&snapshot.path))
+            .map_err(|e| Error::Template(e.to_string()))?;
+
+        let mut snapshots = self.config.snapshots.clone();
+        snapshots.push(snapshot.clone());
+        self.set_snapshots(snapshots)?;
+        Ok(snapshot)
+    }
+
+    pub fn restore(snapshot: &Snapshot) -> Result<(), Error> {
+        load_template(snapshot.schema()?).map_err(|e| Error::Template(e.to_string()))
+    }
+
+    pub fn delete(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        if snapshot.path.exists() {
+            std::fs::remove_file(&snapshot.path)?;
+        }
+        let mut snapshots = self.config.snapshots.clone();
+        snapshots.retain(|l| l != snapshot);
+        self.set_snapshots(snapshots)
+    }
+
+    fn set_snapshots(&mut self, mut snapshots: Vec<Snapshot>) -> Result<(), Error> {
+        snapshots.sort_by(|a, b| {
+            b.created
+                .and_utc()
+                .timestamp()
+                .cmp(&a.created.and_utc().timestamp())
+        });
+        let written = self
+            .config
+            .set_snapshots(&SnapshotsConfig::helper(), snapshots)?;
+        if !written {
+            log::error!("Failed to write snapshots to config");
+        }
+        Ok(())
+    }
 }
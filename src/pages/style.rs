@@ -0,0 +1,182 @@
+use cosmic::{
+    cosmic_theme::{CornerRadii, Density, Spacing},
+    widget, Element, Task,
+};
+
+use crate::{
+    core::{icons, interface_style::InterfaceStyle},
+    fl,
+};
+
+/// Corner radius presets, the same as in COSMIC Settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Roundness {
+    Square,
+    SlightlyRounded,
+    Round,
+}
+
+impl Roundness {
+    pub fn all() -> &'static [Self] {
+        &[Self::Square, Self::SlightlyRounded, Self::Round]
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Square => fl!("square"),
+            Self::SlightlyRounded => fl!("slightly-rounded"),
+            Self::Round => fl!("round"),
+        }
+    }
+
+    pub fn corner_radii(&self) -> CornerRadii {
+        let radii = |xs: f32, s: f32, m: f32, l: f32, xl: f32| CornerRadii {
+            radius_0: [0.0; 4],
+            radius_xs: [xs; 4],
+            radius_s: [s; 4],
+            radius_m: [m; 4],
+            radius_l: [l; 4],
+            radius_xl: [xl; 4],
+        };
+        match self {
+            Self::Square => radii(2.0, 2.0, 2.0, 2.0, 2.0),
+            Self::SlightlyRounded => radii(2.0, 8.0, 8.0, 8.0, 8.0),
+            Self::Round => radii(4.0, 8.0, 16.0, 32.0, 160.0),
+        }
+    }
+}
+
+/// Interface density presets.
+pub fn densities() -> &'static [Density] {
+    &[Density::Compact, Density::Standard, Density::Spacious]
+}
+
+fn density_title(density: &Density) -> String {
+    match density {
+        Density::Compact => fl!("compact"),
+        Density::Standard => fl!("standard"),
+        Density::Spacious => fl!("spacious"),
+    }
+}
+
+pub struct Style {
+    style: InterfaceStyle,
+    roundness_options: Vec<String>,
+    density_options: Vec<String>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            style: InterfaceStyle::current(),
+            roundness_options: Roundness::all().iter().map(Roundness::title).collect(),
+            density_options: densities().iter().map(density_title).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Roundness(usize),
+    Density(usize),
+    OuterGap(u32),
+    InnerGap(u32),
+    /// A gap slider was released, writing the gaps it was dragged to.
+    GapReleased,
+    /// Keep this style when applying color schemes, handled by the app config.
+    KeepStyle(bool),
+}
+
+impl Style {
+    pub fn view<'a>(&'a self, keep_style: bool) -> Element<'a, Message> {
+        let spacing = cosmic::theme::spacing();
+        let roundness = Roundness::all()
+            .iter()
+            .position(|roundness| roundness.corner_radii() == self.style.corner_radii);
+        let density = densities()
+            .iter()
+            .position(|density| Spacing::from(*density) == self.style.spacing);
+        let (outer_gap, inner_gap) = self.style.gaps;
+
+        widget::scrollable(
+            widget::settings::section()
+                .title(fl!("interface-style"))
+                .add(
+                    widget::settings::item::builder(fl!("roundness"))
+                        .description(fl!("roundness-description"))
+                        .icon(icons::get_icon("window-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.roundness_options,
+                            roundness,
+                            Message::Roundness,
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("density"))
+                        .description(fl!("density-description"))
+                        .icon(icons::get_icon("view-grid-symbolic", 18))
+                        .control(widget::dropdown(
+                            &self.density_options,
+                            density,
+                            Message::Density,
+                        )),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("outer-gap"))
+                        .icon(icons::get_icon("resize-mode-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::slider(0..=32, outer_gap, Message::OuterGap)
+                                        .on_release(Message::GapReleased),
+                                )
+                                .push(widget::text::text(format!("{outer_gap} px")))
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("inner-gap"))
+                        .icon(icons::get_icon("size-horizontally-symbolic", 18))
+                        .control(
+                            widget::row()
+                                .push(
+                                    widget::slider(0..=32, inner_gap, Message::InnerGap)
+                                        .on_release(Message::GapReleased),
+                                )
+                                .push(widget::text::text(format!("{inner_gap} px")))
+                                .spacing(spacing.space_xxs),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("keep-style"))
+                        .description(fl!("keep-style-description"))
+                        .toggler(keep_style, Message::KeepStyle),
+                ),
+        )
+        .into()
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<crate::app::message::Message> {
+        match message {
+            Message::Roundness(index) => {
+                self.style.corner_radii = Roundness::all()[index].corner_radii();
+            }
+            Message::Density(index) => self.style.spacing = densities()[index].into(),
+            // Written once the slider is released, not on every step of the drag.
+            Message::OuterGap(gap) => {
+                self.style.gaps.0 = gap;
+                return Task::none();
+            }
+            Message::InnerGap(gap) => {
+                self.style.gaps.1 = gap;
+                return Task::none();
+            }
+            Message::GapReleased => (),
+            Message::KeepStyle(_) => return Task::none(),
+        }
+        if let Err(e) = self.style.write() {
+            log::error!("Error updating the interface style: {e}");
+        }
+        Task::none()
+    }
+}